@@ -1,8 +1,15 @@
 #![allow(dead_code, unused_variables, unused_mut, unused_imports)]
 
+mod matrix;
+mod request_builder;
+mod summarize;
+
 fn main() {
     function_generics::use_largest();
+    function_generics::use_summarize();
     struct_generics::use_point_b_more();
+    const_generics::use_matrix();
+    type_state::use_request_builder();
 }
 
 /// # Generics in Functions p. 174
@@ -53,6 +60,13 @@ mod function_generics {
         println!("the largest number is {}", largest_number);
         println!("the largets string is {}", largest_string);
     }
+
+    /// `largest` has two bounds. See `summarize.rs` for a function with three, written with a
+    /// `where` clause.
+    pub(super) fn use_summarize() {
+        println!("{}", crate::summarize::summarize(&[4, 3, 2, 5, 1]));
+        println!("{}", crate::summarize::summarize(&["foo", "bar", "baz"]));
+    }
 }
 
 /// # Generics in Structs p. 174 and Methods p. 179
@@ -103,11 +117,60 @@ mod struct_generics {
         }
     }
 
+    // This impl block only exists when both fields have the same type, and that type can be
+    // converted into an `f64`. The bounds are what let us do math: with a bare `T` we couldn't
+    // subtract, multiply or take a square root. `Into<f64>` covers `i32`, `u32`, `f32`, `f64` and
+    // the smaller integers, but not `i64` or `u64`, which can't all be stored exactly in an `f64`.
+    // `Copy` lets us call `into()` (which takes `self` by value) without moving out of `&self`.
+    impl<T> PointB<T, T>
+    where
+        T: Into<f64> + Copy,
+    {
+        /// The straight-line (Euclidean) distance between two points.
+        pub fn distance_to(&self, other: &Self) -> f64 {
+            let dx = self.x.into() - other.x.into();
+            let dy = self.y.into() - other.y.into();
+            dx.hypot(dy)
+        }
+    }
+
     pub(super) fn use_point_b_more() {
         // The types can be inferred.
         let point = PointB::new("foo", 1.0);
         println!("point_b x is: {}", point.x());
         println!("point_b y is: {}", point.y());
+
+        // Does not compile: `distance_to` only exists when `T` and `U` are the same type.
+        // point.distance_to(&point);
+
+        let origin = PointB::new(0, 0);
+        println!("distance: {}", origin.distance_to(&PointB::new(3, 4)));
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn distance_between_integer_points() {
+            let a = PointB::new(1, 2);
+            let b = PointB::new(4, 6);
+            assert_eq!(5.0, a.distance_to(&b));
+            assert_eq!(5.0, b.distance_to(&a));
+        }
+
+        #[test]
+        fn distance_between_float_points() {
+            let a = PointB::new(0.0f32, 0.0);
+            let b = PointB::new(1.5f32, 2.0);
+            assert!((a.distance_to(&b) - 2.5).abs() < 1e-9);
+        }
+
+        #[test]
+        fn distance_to_self_is_zero() {
+            let a = PointB::new(-7i16, 3);
+            assert_eq!(0.0, a.distance_to(&a));
+        }
     }
 }
 
@@ -161,6 +224,37 @@ mod partial_impls {
     }
 }
 
+/// # Const Generics
+///
+/// Chapter 9 uses const generics for `Clamp<MIN, MAX>`. See `matrix.rs` for a type where the
+/// const generics are the dimensions of a matrix.
+mod const_generics {
+    use crate::matrix::Matrix;
+
+    pub(super) fn use_matrix() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        println!("a 2x3 matrix: {:?}", m);
+        println!("transposed to 3x2: {:?}", m.transpose());
+        println!("added to itself: {:?}", m + m);
+    }
+}
+
+/// # Generics as Type-State
+///
+/// A generic parameter doesn't have to be data that the struct holds. See `request_builder.rs` for
+/// a builder whose generic parameter tracks whether it is ready to `build`.
+mod type_state {
+    use crate::request_builder::RequestBuilder;
+
+    pub(super) fn use_request_builder() {
+        let builder = RequestBuilder::new().header("Accept", "text/plain");
+        // Does not compile because `builder` is a `RequestBuilder<Missing>`:
+        // let request = builder.build();
+        let request = builder.url("https://example.com").build();
+        println!("built a request: {:?}", request);
+    }
+}
+
 /// # Discussion of Performance p. 181
 ///
 /// - When generics are used, the types being used are "substituted" for the generic params and code
@@ -192,4 +286,90 @@ mod performance {
     fn print_str(value: &str) {
         println!("I'm printing this: {}", value)
     }
+
+    // The alternative to generics is a trait object (`dyn Trait`, chapter 17). Here is the same
+    // function written both ways.
+
+    pub trait Shape {
+        fn area(&self) -> f64;
+    }
+
+    pub struct Circle {
+        pub radius: f64,
+    }
+
+    impl Shape for Circle {
+        fn area(&self) -> f64 {
+            std::f64::consts::PI * self.radius * self.radius
+        }
+    }
+
+    pub struct Square {
+        pub side: f64,
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.side * self.side
+        }
+    }
+
+    /// Static dispatch. A copy of this function is generated for each `S` it is used with, and in
+    /// each copy the call to `area` is a direct call to that type's `area` (which the compiler can
+    /// then inline). The catch is that every item in the slice must be the same type: this can add
+    /// up a `&[Circle]` or a `&[Square]`, but not a mix.
+    pub fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+        shapes.iter().map(|s| s.area()).sum()
+    }
+
+    /// Dynamic dispatch. There is only one copy of this function. Each `Box<dyn Shape>` carries a
+    /// pointer to its type's vtable, and each call to `area` looks up the function there at
+    /// runtime, which also means it can't be inlined. In exchange, the slice can hold a mix of
+    /// shapes.
+    ///
+    /// The difference per call is small. Measure before deciding that it matters; `cargo bench` or
+    /// the `criterion` crate are the tools for that, not a unit test.
+    pub fn total_area_dyn(shapes: &[Box<dyn Shape>]) -> f64 {
+        shapes.iter().map(|s| s.area()).sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn generic_and_dyn_agree() {
+            let sides = [1.0, 2.5, 3.0, 10.0];
+            let squares: Vec<Square> = sides.iter().map(|&side| Square { side }).collect();
+            let boxed: Vec<Box<dyn Shape>> = sides
+                .iter()
+                .map(|&side| Box::new(Square { side }) as Box<dyn Shape>)
+                .collect();
+            assert_eq!(total_area(&squares), total_area_dyn(&boxed));
+            assert_eq!(116.25, total_area(&squares));
+        }
+
+        #[test]
+        fn dyn_can_mix_shapes() {
+            let shapes: Vec<Box<dyn Shape>> = vec![
+                Box::new(Square { side: 2.0 }),
+                Box::new(Circle { radius: 1.0 }),
+            ];
+            let circles = [Circle { radius: 1.0 }];
+            let squares = [Square { side: 2.0 }];
+            // Does not compile: a generic slice can only hold one type.
+            // total_area(&[Square { side: 2.0 }, Circle { radius: 1.0 }]);
+            assert_eq!(
+                total_area(&squares) + total_area(&circles),
+                total_area_dyn(&shapes)
+            );
+        }
+
+        #[test]
+        fn empty() {
+            let none: [Circle; 0] = [];
+            assert_eq!(0.0, total_area(&none));
+            assert_eq!(0.0, total_area_dyn(&[]));
+        }
+    }
 }
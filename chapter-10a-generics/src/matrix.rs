@@ -0,0 +1,83 @@
+use std::ops::Add;
+
+/// # Const Generics
+///
+/// Generic parameters don't have to be types. A `const` generic is a value, such as a `usize`, that
+/// is known at compile time. Here the number of rows `R` and columns `C` are part of the type, so a
+/// `Matrix<2, 3>` and a `Matrix<3, 2>` are different types. Mistakes like adding matrices of
+/// different sizes are caught by the compiler instead of at runtime.
+///
+/// Because the size is known, the data is a plain nested array on the stack. No `Vec` is needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize>([[f64; C]; R]);
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// The dimensions are inferred from the array, e.g. `Matrix::new([[1.0, 2.0]])` is a
+    /// `Matrix<1, 2>`.
+    pub fn new(data: [[f64; C]; R]) -> Self {
+        Self(data)
+    }
+
+    /// Returns `None` if `r` or `c` is out of bounds.
+    pub fn get(&self, r: usize, c: usize) -> Option<f64> {
+        self.0.get(r).and_then(|row| row.get(c)).copied()
+    }
+
+    /// Note the return type: the rows and columns swap places in the type as well as in the data.
+    pub fn transpose(self) -> Matrix<C, R> {
+        let mut data = [[0.0; R]; C];
+        for (r, row) in self.0.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                data[c][r] = *value;
+            }
+        }
+        Matrix(data)
+    }
+}
+
+/// `Add` is only implemented for two matrices with the same `R` and `C`. Trying to add a
+/// `Matrix<2, 2>` to a `Matrix<3, 3>` does not compile (see `tests/ui/matrix_mismatched_add.rs`).
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Matrix<R, C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut data = self.0;
+        for (r, row) in rhs.0.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                data[r][c] += value;
+            }
+        }
+        Matrix(data)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get() {
+        let m = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(Some(6.0), m.get(1, 2));
+        assert_eq!(None, m.get(2, 0));
+        assert_eq!(None, m.get(0, 3));
+    }
+
+    #[test]
+    fn transpose_2x3_to_3x2() {
+        let m: Matrix<2, 3> = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let t: Matrix<3, 2> = m.transpose();
+        assert_eq!(Matrix::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]), t);
+    }
+
+    #[test]
+    fn add_2x2() {
+        let a = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+        let b = Matrix::new([[10.0, 20.0], [30.0, 40.0]]);
+        assert_eq!(Matrix::new([[11.0, 22.0], [33.0, 44.0]]), a + b);
+    }
+}
@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+/// # Type-State Builders
+///
+/// A builder usually checks at runtime that all of the required fields were set, and `build`
+/// returns a `Result`. With generics we can move that check to compile time. The builder carries a
+/// `State` type parameter, and `build` only exists on a `RequestBuilder<Ready>`. The only way to
+/// get one is to call `url`, which consumes the `RequestBuilder<Missing>` and returns a
+/// `RequestBuilder<Ready>`.
+///
+/// The state types are zero-sized markers and are only held in `PhantomData`, so they cost nothing
+/// at runtime.
+pub struct RequestBuilder<State> {
+    url: String,
+    method: String,
+    headers: Vec<(String, String)>,
+    _state: PhantomData<State>,
+}
+
+/// The URL has not been set yet.
+pub struct Missing;
+
+/// The URL has been set, so the request can be built.
+pub struct Ready;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestBuilder<Missing> {
+    pub fn new() -> Self {
+        Self {
+            url: String::new(),
+            method: "GET".to_string(),
+            headers: Vec::new(),
+            _state: PhantomData,
+        }
+    }
+
+    /// This is the state transition. Note that the return type is different from `Self`.
+    pub fn url(self, url: &str) -> RequestBuilder<Ready> {
+        RequestBuilder {
+            url: url.to_string(),
+            method: self.method,
+            headers: self.headers,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl Default for RequestBuilder<Missing> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Optional settings can be set in any state, so these are implemented for every `State`.
+impl<State> RequestBuilder<State> {
+    pub fn method(mut self, method: &str) -> Self {
+        self.method = method.to_string();
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// `build` cannot fail, so it doesn't need to return a `Result`. Calling it on a
+/// `RequestBuilder<Missing>` does not compile (see `tests/ui/build_without_url.rs`).
+impl RequestBuilder<Ready> {
+    pub fn build(self) -> Request {
+        Request {
+            url: self.url,
+            method: self.method,
+            headers: self.headers,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_chain() {
+        let request = RequestBuilder::new()
+            .method("POST")
+            .url("https://example.com")
+            .header("Accept", "application/json")
+            .build();
+        assert_eq!(
+            Request {
+                url: "https://example.com".to_string(),
+                method: "POST".to_string(),
+                headers: vec![("Accept".to_string(), "application/json".to_string())],
+            },
+            request
+        );
+    }
+
+    #[test]
+    fn defaults_to_get() {
+        let request = RequestBuilder::new().url("https://example.com").build();
+        assert_eq!("GET", request.method);
+        assert!(request.headers.is_empty());
+    }
+
+    #[test]
+    fn markers_are_zero_sized() {
+        assert_eq!(
+            size_of::<RequestBuilder<Missing>>(),
+            size_of::<RequestBuilder<Ready>>()
+        );
+        assert_eq!(0, size_of::<PhantomData<Ready>>());
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+/// Describes a slice as `"min=1 max=9 items=3"`.
+///
+/// Each bound is there because the body needs it:
+/// - `PartialOrd` to compare items with `<` and `>` and find the smallest and largest.
+/// - `Clone` because `min_max` returns its own copies rather than references into `items`.
+/// - `Display` to put the items into the string with `{}`.
+///
+/// With this many bounds, a `where` clause is easier to read than cramming them all into the angle
+/// brackets as `summarize<T: Display + Clone + PartialOrd>`. Leave one out and the body doesn't
+/// compile; call it with a type that is missing one and the call doesn't compile. See
+/// `tests/ui/summarize_without_partial_ord.rs`.
+pub fn summarize<T>(items: &[T]) -> String
+where
+    T: Display + Clone + PartialOrd,
+{
+    match min_max(items) {
+        Some((min, max)) => format!("min={} max={} items={}", min, max, items.len()),
+        None => "items=0".to_string(),
+    }
+}
+
+/// Returns copies of the smallest and largest items, or `None` if there are no items.
+///
+/// `PartialOrd` rather than `Ord` means that some values can't be compared, like `f64::NAN`. Every
+/// comparison with one of those is `false`, so they are never picked unless they come first.
+pub fn min_max<T>(items: &[T]) -> Option<(T, T)>
+where
+    T: Clone + PartialOrd,
+{
+    let first = items.first()?;
+    let mut min = first;
+    let mut max = first;
+    for item in items {
+        if item < min {
+            min = item;
+        }
+        if item > max {
+            max = item;
+        }
+    }
+    Some((min.clone(), max.clone()))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_integers() {
+        assert_eq!("min=-2 max=9 items=4", summarize(&[3, 9, -2, 5]));
+    }
+
+    #[test]
+    fn summarize_strs() {
+        // `&str` compares alphabetically (byte by byte, really).
+        assert_eq!(
+            "min=apple max=pear items=3",
+            summarize(&["banana", "pear", "apple"])
+        );
+    }
+
+    #[test]
+    fn summarize_one() {
+        assert_eq!("min=1.5 max=1.5 items=1", summarize(&[1.5]));
+    }
+
+    #[test]
+    fn summarize_empty() {
+        let empty: &[i32] = &[];
+        assert_eq!("items=0", summarize(empty));
+    }
+}
@@ -0,0 +1,10 @@
+// `chapter-10a` is a binary so we can't `use` it here, but we can include the module's source.
+#[path = "../../src/request_builder.rs"]
+mod request_builder;
+
+use request_builder::RequestBuilder;
+
+fn main() {
+    // DOES NOT COMPILE: `build` only exists on `RequestBuilder<Ready>`, and we never called `url`.
+    let _ = RequestBuilder::new().header("Accept", "text/plain").build();
+}
@@ -0,0 +1,12 @@
+// `chapter-10a` is a binary so we can't `use` it here, but we can include the module's source.
+#[path = "../../src/matrix.rs"]
+mod matrix;
+
+use matrix::Matrix;
+
+fn main() {
+    let a: Matrix<2, 2> = Matrix::new([[1.0, 2.0], [3.0, 4.0]]);
+    let b: Matrix<3, 3> = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+    // DOES NOT COMPILE: the dimensions are part of the type, and `Add` requires the same type.
+    let _ = a + b;
+}
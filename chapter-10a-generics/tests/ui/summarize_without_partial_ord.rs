@@ -0,0 +1,21 @@
+// `chapter-10a` is a binary so we can't `use` it here, but we can include the module's source.
+#[path = "../../src/summarize.rs"]
+mod summarize;
+
+use std::fmt::{Display, Formatter};
+use summarize::summarize;
+
+/// `Display` and `Clone`, but not `PartialOrd`.
+#[derive(Clone)]
+struct Color(&'static str);
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn main() {
+    // DOES NOT COMPILE: `summarize` needs to compare the items, and `Color` can't be compared.
+    let _ = summarize(&[Color("red"), Color("blue")]);
+}
@@ -25,8 +25,13 @@ use chapter_10_traits_lib::{Foo, NotMyStruct};
 ///
 fn main() {
     defining_a_trait::do_greetings();
+    clone_on_write::use_normalize_whitespace();
     conditionally_implement_methods::use_conditional_impl();
     blanket_impls::call_add_and_print();
+    operator_overloading::use_operators();
+    index_operator::use_grid();
+    ordering::sort_versions();
+    associated_items::describe_shapes();
 }
 
 /// p. 183
@@ -41,9 +46,14 @@ mod defining_a_trait {
 
         /// This haa a "default" implementation. You can override it, or leave it out (to get the
         /// default) when you impl the trait.
-        fn greet(&self) {
+        fn greeting(&self) -> String {
             // We can use other trait functions in the default implementation.
-            println!("Hello: {}", self.name())
+            format!("Hello: {}", self.name())
+        }
+
+        /// Default implementations can call other default implementations.
+        fn greet(&self) {
+            println!("{}", self.greeting())
         }
     }
 
@@ -73,7 +83,8 @@ mod defining_a_trait {
         }
     }
 
-    /// Here we have a struct that overrides the `greet()` function.
+    /// Here we have a struct that overrides the `greeting()` function. `greet()` still has its
+    /// default implementation, which now prints the overridden greeting.
     struct Different {}
 
     impl Greet for Different {
@@ -82,8 +93,57 @@ mod defining_a_trait {
         }
 
         /// We can override the default implementation.
-        fn greet(&self) {
-            println!("I'm doing something different")
+        fn greeting(&self) -> String {
+            "I'm doing something different".to_string()
+        }
+    }
+
+    /// `FullName`, `Nickname` and `Different` are different types, so they can't go in a
+    /// `Vec<FullName>` together. A `Box<dyn Greet>` is a *trait object*: a pointer to some type
+    /// that implements `Greet`, plus a pointer to that type's `Greet` functions (the "virtual
+    /// function table" mentioned at the top of this file). The actual type is only known at
+    /// runtime, and each call to `greeting()` looks up the function to use. That's *dynamic
+    /// dispatch*, and it's the one place where traits do work like interfaces. Chapter 17 covers
+    /// it properly.
+    #[derive(Default)]
+    pub struct GreeterRegistry {
+        greeters: Vec<Box<dyn Greet>>,
+    }
+
+    impl GreeterRegistry {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// `'static` means the greeter can't borrow anything that might go away before the
+        /// registry does. That's the default for `Box<dyn Greet>`.
+        pub fn register(&mut self, greeter: impl Greet + 'static) {
+            self.greeters.push(Box::new(greeter));
+        }
+
+        pub fn greet_all(&self) -> Vec<String> {
+            self.greeters.iter().map(|g| g.greeting()).collect()
+        }
+    }
+
+    /// `traits_must_be_local` explains the orphan rule: we cannot implement a foreign trait for a
+    /// foreign type. The workaround is the *newtype* pattern, where we wrap the foreign type in a
+    /// tuple struct that we own.
+    ///
+    /// `Greet` is our own trait, so we could implement it for `Vec<String>` directly, but we could
+    /// not implement `Display` (a foreign trait) for `Vec<String>` (a foreign type). Wrapping the
+    /// `Vec` lets us implement both. The wrapper has no runtime cost.
+    pub struct GreetableVec(pub Vec<String>);
+
+    impl Greet for GreetableVec {
+        fn name(&self) -> Cow<'_, str> {
+            Cow::Owned(self.0.join(", "))
+        }
+    }
+
+    impl Display for GreetableVec {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "[{}]", self.0.join(", "))
         }
     }
 
@@ -97,9 +157,143 @@ mod defining_a_trait {
         };
         let different = Different {};
 
+        let friends = GreetableVec(vec!["Leo".to_string(), "Raph".to_string()]);
+
         fullname.greet();
         nickname.greet();
         different.greet();
+        friends.greet();
+
+        let mut registry = GreeterRegistry::new();
+        registry.register(fullname);
+        registry.register(nickname);
+        registry.register(different);
+        registry.register(friends);
+        for greeting in registry.greet_all() {
+            println!("From the registry: {}", greeting);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn greetable_vec() {
+            let friends = GreetableVec(vec![
+                "Leo".to_string(),
+                "Raph".to_string(),
+                "Donnie".to_string(),
+            ]);
+            assert_eq!("Leo, Raph, Donnie", friends.name());
+            assert_eq!("[Leo, Raph, Donnie]", friends.to_string());
+        }
+
+        #[test]
+        fn registry_greets_everyone_in_order() {
+            let mut registry = GreeterRegistry::new();
+            registry.register(FullName {
+                first_name: "Leonardo".to_string(),
+                last_name: "Dunlap".to_string(),
+            });
+            registry.register(Nickname {
+                value: "Leo".to_string(),
+            });
+            registry.register(Different {});
+            assert_eq!(
+                vec![
+                    "Hello: Leonardo Dunlap",
+                    "Hello: Leo",
+                    "I'm doing something different"
+                ],
+                registry.greet_all()
+            );
+        }
+
+        #[test]
+        fn empty_registry() {
+            assert!(GreeterRegistry::new().greet_all().is_empty());
+        }
+    }
+}
+
+/// `Greet::name` returns a `Cow<str>` so that implementors can borrow when they already have the
+/// name and allocate only when they have to build it. Here is the same idea in a standalone
+/// function.
+mod clone_on_write {
+    use std::borrow::Cow;
+
+    /// Collapses every run of whitespace into a single space. Most input is already clean, so in
+    /// that case we hand back the input as `Cow::Borrowed` and nothing is allocated. We only pay
+    /// for a new `String` (`Cow::Owned`) when there is something to fix. The caller gets a type
+    /// that derefs to `&str` either way.
+    pub fn normalize_whitespace(input: &str) -> Cow<'_, str> {
+        if !needs_normalizing(input) {
+            return Cow::Borrowed(input);
+        }
+
+        let mut output = String::with_capacity(input.len());
+        let mut previous_was_whitespace = false;
+        for c in input.chars() {
+            if c.is_whitespace() {
+                if !previous_was_whitespace {
+                    output.push(' ');
+                }
+                previous_was_whitespace = true;
+            } else {
+                output.push(c);
+                previous_was_whitespace = false;
+            }
+        }
+        Cow::Owned(output)
+    }
+
+    /// True if the input has two whitespace characters in a row, or whitespace that isn't a plain
+    /// space (like a tab or newline).
+    fn needs_normalizing(input: &str) -> bool {
+        let mut previous_was_whitespace = false;
+        for c in input.chars() {
+            if c.is_whitespace() && (previous_was_whitespace || c != ' ') {
+                return true;
+            }
+            previous_was_whitespace = c.is_whitespace();
+        }
+        false
+    }
+
+    pub fn use_normalize_whitespace() {
+        for input in ["already clean", "needs  some\t\tcleaning"] {
+            match normalize_whitespace(input) {
+                Cow::Borrowed(s) => println!("borrowed: '{}'", s),
+                Cow::Owned(s) => println!("owned: '{}'", s),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn clean_input_is_borrowed() {
+            let normalized = normalize_whitespace("nothing to do here");
+            assert!(matches!(normalized, Cow::Borrowed(_)));
+            assert_eq!("nothing to do here", normalized);
+        }
+
+        #[test]
+        fn double_spaces_are_owned() {
+            let normalized = normalize_whitespace("too  many   spaces");
+            assert!(matches!(normalized, Cow::Owned(_)));
+            assert_eq!("too many spaces", normalized);
+        }
+
+        #[test]
+        fn tabs_and_newlines_are_owned() {
+            let normalized = normalize_whitespace("tab\tand\nnewline");
+            assert!(matches!(normalized, Cow::Owned(_)));
+            assert_eq!("tab and newline", normalized);
+        }
     }
 }
 
@@ -198,6 +392,59 @@ mod returning_generics_with_trait_bounds {
         // This code path returns an i32, which implements `Display`
         5
     }
+
+    /// Where `impl Trait` in return position really earns its keep is iterators. The real type of
+    /// this iterator is something like:
+    ///
+    /// ```text
+    /// Map<Filter<RangeInclusive<i32>, {closure@src/main.rs:..}>, {closure@src/main.rs:..}>
+    /// ```
+    ///
+    /// That's long, but the real problem is the closures. Every closure has its own anonymous
+    /// type that has no name, so there is nothing we could write in place of `impl Iterator`.
+    /// Before `impl Trait` existed, the only options were to box the iterator (see
+    /// `numbers_boxed`) or to write a struct and implement `Iterator` for it by hand.
+    pub fn numbers() -> impl Iterator<Item = i32> {
+        (1..=10).filter(|n| n % 2 == 0).map(|n| n * n)
+    }
+
+    /// Boxing works too, but it costs an allocation, and each call to `next` goes through dynamic
+    /// dispatch. The upside is that, unlike `impl Iterator`, different branches could return
+    /// different iterator types.
+    pub fn numbers_boxed() -> Box<dyn Iterator<Item = i32>> {
+        Box::new((1..=10).filter(|n| n % 2 == 0).map(|n| n * n))
+    }
+
+    /// `impl Trait` in argument position is the other side of the same coin. The *caller* picks
+    /// the type here, while in return position the *function* picks it and the caller only knows
+    /// that it is some `Iterator`. So the result of `numbers()` can be passed straight in.
+    pub fn sum_all(numbers: impl Iterator<Item = i32>) -> i32 {
+        numbers.sum()
+    }
+
+    // DOES NOT COMPILE: we can't write the type of the iterator in a `let` or a struct field.
+    // let iter: Map<Filter<RangeInclusive<i32>, ???>, ???> = numbers();
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn numbers_are_even_squares() {
+            assert_eq!(vec![4, 16, 36, 64, 100], numbers().collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn boxed_is_the_same() {
+            assert!(numbers().eq(numbers_boxed()));
+        }
+
+        #[test]
+        fn return_position_into_argument_position() {
+            assert_eq!(220, sum_all(numbers()));
+            assert_eq!(6, sum_all(vec![1, 2, 3].into_iter()));
+        }
+    }
 }
 
 /// Trait Bounds to Conditionally Implement Methods p. 191
@@ -290,6 +537,569 @@ mod blanket_impls {
     }
 }
 
+/// Operator Overloading
+///
+/// `blanket_impls` uses `Add` as a trait bound. Here we implement the operator traits from
+/// `std::ops` ourselves so that `+`, `-`, `*` and `+=` work on our own type.
+mod operator_overloading {
+    use std::ops::{Add, AddAssign, Mul, Sub};
+
+    /// `Default` gives us the zero vector, which `accumulate` starts from.
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    pub struct Vector2 {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    impl Add for Vector2 {
+        type Output = Vector2;
+
+        fn add(self, rhs: Vector2) -> Vector2 {
+            Vector2 {
+                x: self.x + rhs.x,
+                y: self.y + rhs.y,
+            }
+        }
+    }
+
+    impl Sub for Vector2 {
+        type Output = Vector2;
+
+        fn sub(self, rhs: Vector2) -> Vector2 {
+            Vector2 {
+                x: self.x - rhs.x,
+                y: self.y - rhs.y,
+            }
+        }
+    }
+
+    /// The `Output` does not have to be `Self`. Multiplying two vectors gives us their dot
+    /// product, which is a plain number.
+    impl Mul for Vector2 {
+        type Output = f64;
+
+        fn mul(self, rhs: Vector2) -> f64 {
+            self.x * rhs.x + self.y * rhs.y
+        }
+    }
+
+    /// The right-hand side does not have to be `Self` either. `Mul` has a generic parameter,
+    /// `Mul<Rhs = Self>`, so we can implement it a second time for scaling by an `f64`.
+    impl Mul<f64> for Vector2 {
+        type Output = Vector2;
+
+        fn mul(self, rhs: f64) -> Vector2 {
+            Vector2 {
+                x: self.x * rhs,
+                y: self.y * rhs,
+            }
+        }
+    }
+
+    /// `+=` is a separate trait because it modifies the left-hand side in place instead of
+    /// producing a new value.
+    impl AddAssign for Vector2 {
+        fn add_assign(&mut self, rhs: Vector2) {
+            self.x += rhs.x;
+            self.y += rhs.y;
+        }
+    }
+
+    /// Adds up all of the `items`. This works for any type that can be added to itself, including
+    /// `Vector2`, because `+` is just a call to `Add::add`.
+    ///
+    /// The bounds are everything the body needs and nothing more:
+    /// - `Add<Output = T>` so that adding two `T`s gives another `T` that we can keep adding to.
+    ///   (`Mul for Vector2` wouldn't do, its `Output` is `f64`.)
+    /// - `Default` for the starting value, which is zero for the numbers. It's also what an empty
+    ///   slice gives back.
+    /// - `Copy` so that we can take the items out of the slice. `Add::add` takes `self` by value,
+    ///   and we only have a `&T`. `Clone` would also work, with a `.clone()` for each item.
+    ///
+    /// `Iterator::sum` does the same thing with the `Sum` trait instead.
+    pub fn accumulate<T: Add<Output = T> + Default + Copy>(items: &[T]) -> T {
+        let mut total = T::default();
+        for &item in items {
+            total = total + item;
+        }
+        total
+    }
+
+    pub fn use_operators() {
+        let a = Vector2 { x: 1.0, y: 2.0 };
+        let b = Vector2 { x: 3.0, y: 4.0 };
+        let mut c = a + b;
+        c += a;
+        println!("a + b + a = {:?}", c);
+        println!("b - a = {:?}", b - a);
+        println!("a * 2.0 = {:?}", a * 2.0);
+        println!("a · b = {}", a * b);
+        println!("accumulate(&[a, b, c]) = {:?}", accumulate(&[a, b, c]));
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn add() {
+            let sum = Vector2 { x: 1.0, y: 2.0 } + Vector2 { x: 3.0, y: 4.0 };
+            assert_eq!(Vector2 { x: 4.0, y: 6.0 }, sum);
+        }
+
+        #[test]
+        fn sub() {
+            let difference = Vector2 { x: 1.0, y: 2.0 } - Vector2 { x: 3.0, y: 5.0 };
+            assert_eq!(Vector2 { x: -2.0, y: -3.0 }, difference);
+        }
+
+        #[test]
+        fn mul_scalar() {
+            let scaled = Vector2 { x: 1.5, y: -2.0 } * 2.0;
+            assert_eq!(Vector2 { x: 3.0, y: -4.0 }, scaled);
+        }
+
+        #[test]
+        fn mul_dot_product() {
+            let dot = Vector2 { x: 1.0, y: 2.0 } * Vector2 { x: 3.0, y: 4.0 };
+            assert_eq!(11.0, dot);
+        }
+
+        #[test]
+        fn add_assign() {
+            let mut v = Vector2 { x: 1.0, y: 2.0 };
+            v += Vector2 { x: 0.5, y: 0.5 };
+            assert_eq!(Vector2 { x: 1.5, y: 2.5 }, v);
+        }
+
+        #[test]
+        fn accumulate_i32() {
+            assert_eq!(6, accumulate(&[1, 2, 3]));
+            assert_eq!(-4, accumulate(&[-5, 1]));
+        }
+
+        #[test]
+        fn accumulate_f64() {
+            assert_eq!(4.0, accumulate(&[0.5, 1.5, 2.0]));
+        }
+
+        #[test]
+        fn accumulate_empty_is_the_default() {
+            assert_eq!(0, accumulate::<i32>(&[]));
+            assert_eq!(0.0, accumulate::<f64>(&[]));
+            assert_eq!(Vector2::default(), accumulate::<Vector2>(&[]));
+        }
+
+        #[test]
+        fn accumulate_custom_type() {
+            let vectors = [
+                Vector2 { x: 1.0, y: 2.0 },
+                Vector2 { x: 3.0, y: 4.0 },
+                Vector2 { x: -0.5, y: 0.0 },
+            ];
+            assert_eq!(Vector2 { x: 3.5, y: 6.0 }, accumulate(&vectors));
+        }
+    }
+}
+
+/// Index Operator Overloading
+///
+/// `container[index]` is also an operator. `Index` is used for reading and `IndexMut` for writing.
+/// The index doesn't have to be a `usize`, here it is a `(row, column)` tuple.
+mod index_operator {
+    use std::ops::{Index, IndexMut};
+
+    /// A 2D grid stored in a single `Vec`, one row after another.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Grid<T> {
+        data: Vec<T>,
+        width: usize,
+    }
+
+    impl<T: Clone> Grid<T> {
+        pub fn new(width: usize, height: usize, value: T) -> Self {
+            Self {
+                data: vec![value; width * height],
+                width,
+            }
+        }
+    }
+
+    impl<T> Grid<T> {
+        pub fn width(&self) -> usize {
+            self.width
+        }
+
+        pub fn height(&self) -> usize {
+            self.data.len().checked_div(self.width).unwrap_or(0)
+        }
+
+        /// Like `Vec::get`, returns `None` instead of panicking when out of bounds. Note that we
+        /// must check the column ourselves: `(0, width)` would otherwise land on the start of the
+        /// next row. We check the row too, before doing any math, because a huge row would make
+        /// `row * width` overflow, which panics in a debug build.
+        pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+            if row >= self.height() || col >= self.width {
+                return None;
+            }
+            self.data.get(row * self.width + col)
+        }
+
+        fn offset(&self, (row, col): (usize, usize)) -> usize {
+            assert!(
+                row < self.height() && col < self.width,
+                "index ({}, {}) out of bounds for a {}x{} grid",
+                row,
+                col,
+                self.height(),
+                self.width
+            );
+            row * self.width + col
+        }
+    }
+
+    /// `grid[(row, col)]` returns a reference to the cell, and panics if it is out of bounds, just
+    /// like indexing a `Vec` does.
+    impl<T> Index<(usize, usize)> for Grid<T> {
+        type Output = T;
+
+        fn index(&self, index: (usize, usize)) -> &T {
+            &self.data[self.offset(index)]
+        }
+    }
+
+    /// `grid[(row, col)] = value` uses `IndexMut`. It has no `Output` of its own, it reuses the one
+    /// from `Index`.
+    impl<T> IndexMut<(usize, usize)> for Grid<T> {
+        fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+            let offset = self.offset(index);
+            &mut self.data[offset]
+        }
+    }
+
+    pub fn use_grid() {
+        let mut grid = Grid::new(3, 2, '.');
+        grid[(0, 0)] = '#';
+        grid[(1, 2)] = '#';
+        for row in 0..grid.height() {
+            let line: String = (0..grid.width()).map(|col| grid[(row, col)]).collect();
+            println!("{}", line);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn read_and_write() {
+            let mut grid = Grid::new(3, 2, 0);
+            grid[(1, 2)] = 7;
+            assert_eq!(7, grid[(1, 2)]);
+            assert_eq!(0, grid[(0, 2)]);
+            assert_eq!(0, grid[(1, 1)]);
+        }
+
+        #[test]
+        fn index_mut_in_place() {
+            let mut grid = Grid::new(2, 2, 1);
+            grid[(0, 1)] += 1;
+            assert_eq!(2, grid[(0, 1)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "out of bounds")]
+        fn index_out_of_bounds() {
+            let grid = Grid::new(3, 2, 0);
+            let _ = grid[(2, 0)];
+        }
+
+        #[test]
+        #[should_panic(expected = "out of bounds")]
+        fn index_column_out_of_bounds() {
+            let grid = Grid::new(3, 2, 0);
+            let _ = grid[(0, 3)];
+        }
+
+        #[test]
+        fn get_in_bounds() {
+            let grid = Grid::new(3, 2, 5);
+            assert_eq!(Some(&5), grid.get((1, 2)));
+        }
+
+        #[test]
+        fn get_out_of_bounds() {
+            let grid = Grid::new(3, 2, 5);
+            assert_eq!(None, grid.get((2, 0)));
+            assert_eq!(None, grid.get((0, 3)));
+        }
+
+        #[test]
+        fn get_huge_row() {
+            let grid = Grid::new(3, 2, 5);
+            assert_eq!(None, grid.get((usize::MAX, 0)));
+            assert_eq!(None, grid.get((usize::MAX / 2, 1)));
+        }
+    }
+}
+
+/// Ordering
+///
+/// `sort`, `max`, `BTreeMap` and friends need to know how to compare things. That's what
+/// `PartialOrd` and `Ord` are for. `#[derive(PartialOrd, Ord)]` compares the fields in the order
+/// they are declared, which happens to be right for `Version`, but we write it out by hand to see
+/// how it works.
+mod ordering {
+    use std::cmp::Ordering;
+    use std::fmt::{Display, Formatter};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Version {
+        pub major: u32,
+        pub minor: u32,
+        pub patch: u32,
+    }
+
+    impl Version {
+        pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+            Self {
+                major,
+                minor,
+                patch,
+            }
+        }
+    }
+
+    impl Display for Version {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+
+    /// `Ord` is a total order: any two versions are either less, equal or greater. `then_with` only
+    /// looks at the next field when the previous ones were equal. Because the fields are numbers,
+    /// `1.10.0` comes after `1.2.0`, which a string comparison would get wrong.
+    impl Ord for Version {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.major
+                .cmp(&other.major)
+                .then_with(|| self.minor.cmp(&other.minor))
+                .then_with(|| self.patch.cmp(&other.patch))
+        }
+    }
+
+    /// `PartialOrd` is for types where some values can't be compared (like `f64::NAN`). Every
+    /// `Ord` type must also be `PartialOrd`, and the two must agree, so we just call `cmp`.
+    impl PartialOrd for Version {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    pub fn sort_versions() {
+        let mut versions = [
+            Version::new(1, 10, 0),
+            Version::new(1, 2, 3),
+            Version::new(0, 9, 9),
+            Version::new(1, 2, 0),
+        ];
+        versions.sort();
+        let sorted: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        println!("sorted versions: {}", sorted.join(", "));
+
+        let mut strings = ["1.10.0", "1.2.3", "0.9.9", "1.2.0"];
+        strings.sort();
+        println!("sorted strings: {}", strings.join(", "));
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn sort_is_numeric() {
+            let mut versions = vec![
+                Version::new(1, 10, 0),
+                Version::new(1, 2, 3),
+                Version::new(1, 2, 0),
+            ];
+            versions.sort();
+            assert_eq!(
+                vec![
+                    Version::new(1, 2, 0),
+                    Version::new(1, 2, 3),
+                    Version::new(1, 10, 0)
+                ],
+                versions
+            );
+        }
+
+        #[test]
+        fn strings_sort_lexically() {
+            let mut strings = vec!["1.10.0", "1.2.3", "1.2.0"];
+            strings.sort();
+            assert_eq!(vec!["1.10.0", "1.2.0", "1.2.3"], strings);
+        }
+
+        #[test]
+        fn major_wins() {
+            assert!(Version::new(2, 0, 0) > Version::new(1, 99, 99));
+        }
+
+        #[test]
+        fn patch_breaks_ties() {
+            assert_eq!(
+                Ordering::Less,
+                Version::new(1, 2, 0).cmp(&Version::new(1, 2, 3))
+            );
+        }
+
+        #[test]
+        fn equal() {
+            assert_eq!(
+                Ordering::Equal,
+                Version::new(1, 2, 3).cmp(&Version::new(1, 2, 3))
+            );
+        }
+
+        #[test]
+        fn max() {
+            let newest = [Version::new(1, 9, 0), Version::new(1, 10, 0)]
+                .into_iter()
+                .max();
+            assert_eq!(Some(Version::new(1, 10, 0)), newest);
+        }
+    }
+}
+
+/// Associated Constants and Associated Types
+///
+/// Traits can declare constants and types in addition to functions. The implementor fills them in.
+/// Unlike a generic parameter on the trait, there can only be one choice per implementing type.
+mod associated_items {
+    /// Every `Shape` has a fixed number of sides, so it makes sense as an associated constant. We
+    /// can read it from the type alone, without an instance, e.g. `Square::SIDES`.
+    pub trait Shape {
+        const SIDES: u32;
+
+        fn area(&self) -> f64;
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Square {
+        pub side: f64,
+    }
+
+    impl Shape for Square {
+        const SIDES: u32 = 4;
+
+        fn area(&self) -> f64 {
+            self.side * self.side
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Triangle {
+        pub base: f64,
+        pub height: f64,
+    }
+
+    impl Shape for Triangle {
+        const SIDES: u32 = 3;
+
+        fn area(&self) -> f64 {
+            0.5 * self.base * self.height
+        }
+    }
+
+    /// The constant is available through the generic type `S`.
+    pub fn describe<S: Shape>(s: &S) {
+        println!(
+            "A shape with {} sides and an area of {}",
+            S::SIDES,
+            s.area()
+        );
+    }
+
+    /// An associated type is a placeholder that the implementor chooses. `Iterator::Item` is the
+    /// one we see the most. Here, scaling a shape gives back the same kind of shape, and each
+    /// implementor says what that type is.
+    pub trait Scale {
+        type Output;
+
+        fn scale(&self, factor: f64) -> Self::Output;
+    }
+
+    impl Scale for Square {
+        type Output = Square;
+
+        fn scale(&self, factor: f64) -> Square {
+            Square {
+                side: self.side * factor,
+            }
+        }
+    }
+
+    impl Scale for Triangle {
+        type Output = Triangle;
+
+        fn scale(&self, factor: f64) -> Triangle {
+            Triangle {
+                base: self.base * factor,
+                height: self.height * factor,
+            }
+        }
+    }
+
+    pub fn describe_shapes() {
+        let square = Square { side: 2.0 };
+        describe(&square);
+        describe(&square.scale(2.0));
+        describe(&Triangle {
+            base: 3.0,
+            height: 4.0,
+        });
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn square() {
+            let square = Square { side: 3.0 };
+            assert_eq!(9.0, square.area());
+            assert_eq!(4, Square::SIDES);
+        }
+
+        #[test]
+        fn triangle() {
+            let triangle = Triangle {
+                base: 3.0,
+                height: 4.0,
+            };
+            assert_eq!(6.0, triangle.area());
+            assert_eq!(3, Triangle::SIDES);
+        }
+
+        #[test]
+        fn scale_returns_the_associated_type() {
+            let square: Square = Square { side: 3.0 }.scale(2.0);
+            assert_eq!(36.0, square.area());
+        }
+    }
+}
+
+/// Supertraits
+///
+/// Rust does not have inheritance, but a trait can require that its implementors also implement
+/// another trait. This is the closest thing we have to an "is-a" relationship. The default
+/// implementations in the subtrait can then call functions from the supertrait.
+///
+/// Implementing `Greetable` for a type that does not implement `Named` does not compile. See
+/// `tests/ui/greetable_without_named.rs`, which is checked by `trybuild` in `tests/compile_fail.rs`.
+mod supertraits;
+
 pub struct MyStruct {}
 
 // We can implement a foreign Trait on our own type
@@ -0,0 +1,40 @@
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+/// `Greetable: Named` means "anything that is `Greetable` must also be `Named`".
+pub trait Greetable: Named {
+    fn greet(&self) -> String {
+        format!("Hi {}", self.name())
+    }
+}
+
+pub struct Dog {
+    pub name: String,
+}
+
+impl Named for Dog {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+// We get `greet` for free, but only because we also implemented `Named`.
+impl Greetable for Dog {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_greet_uses_supertrait_name() {
+        let dog = Dog {
+            name: "Rex".to_string(),
+        };
+        assert_eq!("Hi Rex", dog.greet());
+    }
+}
@@ -0,0 +1,13 @@
+// `chapter-10b-traits` is a binary so we can't `use` it here, but we can include the module's
+// source.
+#[path = "../../src/supertraits.rs"]
+mod supertraits;
+
+use supertraits::Greetable;
+
+struct Cat;
+
+// DOES NOT COMPILE: `Cat` must implement `Named` before it can implement `Greetable`.
+impl Greetable for Cat {}
+
+fn main() {}
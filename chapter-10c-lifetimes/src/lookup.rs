@@ -0,0 +1,92 @@
+/// A tiny map that borrows its keys and values instead of owning them.
+///
+/// `Lookup<'a>` says "this struct holds references that are valid for `'a`", so every `&'a str`
+/// handed to `insert` has to live at least as long as the `Lookup` is used. The compiler checks
+/// this at every call site. If a key is dropped while the `Lookup` is still around, the program
+/// doesn't compile (see `tests/ui/lookup_outlives_key.rs`).
+///
+/// Borrowing means no copies of the text are made, which is nice when the strings come from one
+/// big buffer (like lines of a file) that outlives the lookup anyway. If that isn't the case, own
+/// the data with `String` instead and the lifetime goes away.
+#[derive(Debug, Default)]
+pub struct Lookup<'a> {
+    entries: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Lookup<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry. A key that is already there is replaced.
+    pub fn insert(&mut self, k: &'a str, v: &'a str) {
+        match self.entries.iter_mut().find(|(key, _)| *key == k) {
+            Some(entry) => entry.1 = v,
+            None => self.entries.push((k, v)),
+        }
+    }
+
+    /// Note the two different lifetimes. The key we look up with only has to live for this call,
+    /// so it gets an elided lifetime of its own. The value we return is one of the stored `&'a str`
+    /// values, so it is valid for `'a`, not just for as long as we borrow `self`. It can outlive
+    /// both the `Lookup` and the `k` that found it.
+    pub fn get(&self, k: &str) -> Option<&'a str> {
+        self.entries
+            .iter()
+            .find(|(key, _)| *key == k)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_from_owned_strings() {
+        let config = String::from("host=localhost\nport=8080");
+        let mut lookup = Lookup::new();
+        for line in config.lines() {
+            let (k, v) = line.split_once('=').unwrap();
+            lookup.insert(k, v);
+        }
+        assert_eq!(2, lookup.len());
+        assert_eq!(Some("localhost"), lookup.get("host"));
+        assert_eq!(Some("8080"), lookup.get("port"));
+        assert_eq!(None, lookup.get("user"));
+    }
+
+    #[test]
+    fn insert_replaces() {
+        let mut lookup = Lookup::new();
+        lookup.insert("a", "1");
+        lookup.insert("a", "2");
+        assert_eq!(1, lookup.len());
+        assert_eq!(Some("2"), lookup.get("a"));
+    }
+
+    #[test]
+    fn value_outlives_the_lookup_and_the_key() {
+        let value = String::from("value");
+        let found = {
+            let mut lookup = Lookup::new();
+            lookup.insert("key", &value);
+            let key = String::from("key");
+            lookup.get(&key)
+            // `lookup` and `key` are dropped here, but `found` borrows from `value`.
+        };
+        assert_eq!(Some("value"), found);
+    }
+}
@@ -3,6 +3,12 @@
 fn main() {}
 
 use std::fmt::Display;
+use std::thread;
+
+mod lookup;
+mod store;
+
+use store::store_static;
 
 /// Lifetimes allow the Rust compiler to prevent dangling references at compile time. This is one
 /// of the major inventions of the Rust programming language.
@@ -163,3 +169,186 @@ impl<'a> WithLifetime<'a> {
         self.something
     }
 }
+
+// See `lookup.rs` for a struct that holds many references, and a method that returns one of them
+// with the struct's lifetime rather than the lifetime of `&self`.
+
+/// Here is the book's `ImportantExcerpt` with a method that has two input references.
+struct Excerpt<'a> {
+    part: &'a str,
+}
+
+impl<'a> Excerpt<'a> {
+    /// The elision rules are:
+    /// 1. Each input reference gets its own lifetime. Here that's `&'x self` and `&'y str`.
+    /// 2. If there is exactly one input lifetime, it is given to all output references.
+    /// 3. If one of the inputs is `&self` or `&mut self`, its lifetime is given to all output
+    ///    references.
+    ///
+    /// Without annotation, rule 3 would make the return type `&'x str`, which is only as long as
+    /// the borrow of the `Excerpt`. That compiles, but it's more restrictive than it needs to be.
+    /// `part` actually lives for `'a`, the lifetime of the text the `Excerpt` points into, so we
+    /// say so explicitly. Now the returned `&str` can outlive the `Excerpt` itself.
+    fn announce_and_return_part(&self, announcement: &str) -> &'a str {
+        println!("Attention please: {announcement}");
+        self.part
+    }
+}
+
+#[cfg(does_not_compile)]
+/// Rule 3 ties the output to `&self`, not to `announcement`, so returning `announcement` does not
+/// compile without an explicit lifetime on it.
+impl<'a> Excerpt<'a> {
+    fn return_announcement(&self, announcement: &str) -> &str {
+        announcement
+    }
+}
+
+/// The book ends the chapter with an example that uses generic type parameters, trait bounds and
+/// lifetimes all at once. Lifetimes are a kind of generic, so they go in the same `<>` list, and
+/// by convention they come first.
+///
+/// `ann` is not a reference and is not part of the output, so it doesn't need a lifetime.
+fn longest_with_announcement<'a, T: Display>(x: &'a str, y: &'a str, ann: T) -> &'a str {
+    println!("Announcement! {ann}");
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+/// # The `'static` Lifetime
+///
+/// `'static` means the reference can live for the entire duration of the program. All string
+/// literals are `&'static str` because the text is baked into the program's binary.
+///
+/// `store_static` (in `store.rs`) keeps the reference in a global, which lives for the whole
+/// program, so the compiler needs to know that the reference will too.
+fn use_store_static() {
+    // A string literal is `&'static str`, so this is fine.
+    store_static("I live forever");
+
+    let local = String::from("I only live until the end of this function");
+    // Does not compile because `&local` is not `'static` (see `tests/ui/store_non_static.rs`):
+    // store_static(&local);
+}
+
+/// A `T: 'static` bound is different from a `&'static` reference, and this confuses a lot of
+/// people. It means that `T` does not *borrow* anything that could go away, not that `T` lives
+/// forever. An owned `String` satisfies `T: 'static` because it doesn't borrow anything, even
+/// though it will be dropped like any other value.
+///
+/// `thread::spawn` requires `F: 'static` because the new thread might outlive the caller, so the
+/// closure can't borrow from the caller's stack. That's why we so often see `move` closures there.
+fn spawn_and_print<T: Display + Send + 'static>(value: T) -> thread::JoinHandle<String> {
+    thread::spawn(move || value.to_string())
+}
+
+fn use_spawn_and_print() {
+    // An owned `String` is `'static` in the `T: 'static` sense.
+    let owned = String::from("owned");
+    let handle = spawn_and_print(owned);
+    println!("{}", handle.join().unwrap());
+
+    // A borrowed `&String` is not. This does not compile:
+    // let local = String::from("borrowed");
+    // spawn_and_print(&local);
+}
+
+/// # Higher-Ranked Trait Bounds
+///
+/// `f` is called on a `String` that is created *inside* this function. If we wrote the bound with
+/// a lifetime parameter on the function, `fn apply_to_ref<'a, F: Fn(&'a str) -> &'a str>`, the
+/// *caller* would choose `'a`, and no lifetime the caller can name is short enough to fit a local
+/// variable of ours. That version does not compile.
+///
+/// `for<'a>` (read it as "for all `'a`") says something different: `f` must work for *every*
+/// lifetime, whatever the borrow happens to be, and the output lives as long as the input. That
+/// is exactly what `|s| s.trim()` does, so we can call it on any borrow we like.
+///
+/// Most of the time you don't have to write this. `F: Fn(&str) -> &str` is elided to the same
+/// `for<'a>` bound. Writing it out shows what the elided version means, and it is needed when the
+/// lifetimes can't be elided, e.g. when a trait has a lifetime parameter of its own.
+fn apply_to_ref<F>(f: F) -> String
+where
+    F: for<'a> Fn(&'a str) -> &'a str,
+{
+    let local = String::from("   only lives inside apply_to_ref   ");
+    f(&local).to_string()
+}
+
+fn use_apply_to_ref() {
+    println!("{}", apply_to_ref(|s| s.trim()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fmt::Formatter;
+
+    /// Records whether it has been formatted, so we can tell that the announcement was printed.
+    struct Announcement<'a> {
+        text: &'static str,
+        displayed: &'a Cell<bool>,
+    }
+
+    impl Display for Announcement<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            self.displayed.set(true);
+            Display::fmt(self.text, f)
+        }
+    }
+
+    #[test]
+    fn longest_with_announcement_returns_longest() {
+        let result = longest_with_announcement("short", "much longer", "hello");
+        assert_eq!("much longer", result);
+    }
+
+    #[test]
+    fn longest_with_announcement_prints_announcement() {
+        let displayed = Cell::new(false);
+        let announcement = Announcement {
+            text: "the longest string is...",
+            displayed: &displayed,
+        };
+        let result = longest_with_announcement("abcd", "xyz", announcement);
+        assert_eq!("abcd", result);
+        assert!(displayed.get());
+    }
+
+    #[test]
+    fn part_outlives_the_excerpt() {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        let part = {
+            let excerpt = Excerpt {
+                part: novel.split('.').next().unwrap(),
+            };
+            let announcement = String::from("the first sentence");
+            excerpt.announce_and_return_part(&announcement)
+            // `excerpt` and `announcement` are dropped here, but `part` borrows from `novel`.
+        };
+        assert_eq!("Call me Ishmael", part);
+    }
+
+    #[test]
+    fn apply_to_ref_trims() {
+        assert_eq!("only lives inside apply_to_ref", apply_to_ref(|s| s.trim()));
+    }
+
+    #[test]
+    fn apply_to_ref_can_return_part_of_the_input() {
+        assert_eq!(
+            "only",
+            apply_to_ref(|s| s.split_whitespace().next().unwrap())
+        );
+    }
+
+    #[test]
+    fn owned_string_satisfies_static_bound() {
+        let handle = spawn_and_print(String::from("owned"));
+        assert_eq!("owned", handle.join().unwrap());
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Mutex;
+
+/// A global, which lives for the whole program. Anything stored in it has to live that long too,
+/// which is what `'static` says.
+pub static STORE: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Only takes references that are valid for the whole program, because `STORE` keeps them forever.
+/// A string literal works, a reference to a local `String` does not (see
+/// `tests/ui/store_non_static.rs`).
+pub fn store_static(s: &'static str) {
+    STORE.lock().unwrap().push(s);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_static_accepts_a_literal() {
+        store_static("a string literal");
+        assert!(STORE.lock().unwrap().contains(&"a string literal"));
+    }
+}
@@ -0,0 +1,16 @@
+// `chapter-10c-lifetimes` is a binary, so we can't import from it here, but we can include the
+// module's source.
+#[path = "../../src/lookup.rs"]
+mod lookup;
+
+use lookup::Lookup;
+
+fn main() {
+    let mut lookup = Lookup::new();
+    {
+        let key = String::from("short-lived");
+        // DOES NOT COMPILE: `key` is dropped at the end of this block, but `lookup` is used after.
+        lookup.insert(&key, "value");
+    }
+    println!("{:?}", lookup.get("short-lived"));
+}
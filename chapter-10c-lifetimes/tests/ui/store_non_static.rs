@@ -0,0 +1,12 @@
+// `chapter-10c-lifetimes` is a binary, so we can't import from it here, but we can include the
+// module's source.
+#[path = "../../src/store.rs"]
+mod store;
+
+use store::store_static;
+
+fn main() {
+    let local = String::from("I only live until the end of main");
+    // DOES NOT COMPILE: `&local` borrows a local, so it is not `'static`.
+    store_static(&local);
+}
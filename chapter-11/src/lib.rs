@@ -220,30 +220,49 @@ mod be_assertive {
 /// # Should Panic
 /// `should_panic` is of limited value unless you are careful.
 mod should_panic_tests {
-    fn gimme_a_value_greater_than_10(x: u8) -> u8 {
-        // CAUTION: this can panic if the subtraction would wrap
-        let result = x - 1;
-        if x < 10 {
+    /// Returns one less than `x`, which must be from 1 to 10.
+    ///
+    /// This used to subtract first (`x - 1`) and check the range afterwards, so `0` panicked with
+    /// "attempt to subtract with overflow" before the range check ever ran. A `should_panic` test
+    /// without `expected` was happy with that. Now the range is checked first, and `checked_sub`
+    /// makes it clear that the subtraction can't overflow once the range check has passed.
+    fn gimme_a_value_up_to_10(x: u8) -> u8 {
+        if x > 10 {
             panic!("'{}' is too high", x);
         }
-        result
+        match x.checked_sub(1) {
+            Some(result) => result,
+            None => panic!("'{}' is too low", x),
+        }
     }
 
-    // This test passes even though it is panicking for a different reason.
-    // This panic says "attempt to subtract with overflow"
+    // This test passes no matter why the function panics, it doesn't check the message. When the
+    // function had the subtraction bug, this passed because of "attempt to subtract with overflow".
     #[test]
     #[should_panic]
     fn unreliable_test() {
-        let _ = gimme_a_value_greater_than_10(0);
+        let _ = gimme_a_value_up_to_10(0);
     }
 
-    // This test fails because we expected it to panic on the range check. Instead it panicked for a
-    // different reason and we want to know this.
+    // We specify a substring from the expected panic message, so this only passes if the range
+    // check is what panicked.
     #[test]
-    // We specify a substring from the expected panic message.
     #[should_panic(expected = "too high")]
     fn reliable_test() {
-        let _ = gimme_a_value_greater_than_10(0);
+        let _ = gimme_a_value_up_to_10(11);
+    }
+
+    // With the subtraction bug, this failed because the overflow panic came first and its message
+    // doesn't contain "too low".
+    #[test]
+    #[should_panic(expected = "too low")]
+    fn zero_is_too_low_not_an_overflow() {
+        let _ = gimme_a_value_up_to_10(0);
+    }
+
+    #[test]
+    fn in_range() {
+        assert_eq!(9, gimme_a_value_up_to_10(10));
     }
 }
 
@@ -251,7 +270,9 @@ mod should_panic_tests {
 ///
 /// The test system can handle tests that return a `Result`.
 mod tests_returning_results {
-    use std::fs::read_to_string;
+    use anyhow::Context;
+    use std::fs::{self, read_to_string};
+    use std::path::Path;
 
     type Result = std::result::Result<(), anyhow::Error>;
 
@@ -262,6 +283,43 @@ mod tests_returning_results {
         assert_eq!("", s);
         Ok(())
     }
+
+    /// The problem above is that the `io::Error` only says "No such file or directory", not which
+    /// file or what we were trying to do. `anyhow::Context` fixes that by wrapping the error with a
+    /// message at each `?`. When a test returns `Err`, the test harness prints the error with its
+    /// `Debug` impl, and `anyhow`'s `Debug` shows every message in the chain:
+    ///
+    /// ```text
+    /// Error: unable to read 'bad/path/does/not/exit'
+    ///
+    /// Caused by:
+    ///     No such file or directory (os error 2)
+    /// ```
+    fn read_file(path: &Path) -> anyhow::Result<String> {
+        read_to_string(path).with_context(|| format!("unable to read '{}'", path.display()))
+    }
+
+    /// Result-returning tests done well: every `?` says what it was doing.
+    #[test]
+    fn returns_a_result_with_context() -> Result {
+        let dir = tempfile::tempdir().context("unable to create a temp dir")?;
+        let path = dir.path().join("greeting.txt");
+        fs::write(&path, "Hello, tests!\n")
+            .with_context(|| format!("unable to write '{}'", path.display()))?;
+
+        let s = read_file(&path)?;
+        assert_eq!("Hello, tests!\n", s);
+        Ok(())
+    }
+
+    /// This is what `returns_a_result` would have told us with context added. `{:#}` prints the
+    /// chain on one line.
+    #[test]
+    fn context_names_the_file() {
+        let err = read_file(Path::new("bad/path/does/not/exit")).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.starts_with("unable to read 'bad/path/does/not/exit': "));
+    }
 }
 
 /// # No Capture
@@ -297,7 +355,9 @@ mod output {
 /// - It works on any machine? It's a `unit test`.
 /// - It requires some setup on the host machine or elsewhere? It's an `integration test`.
 ///
-/// A great way to separate them is to put integration tests behind a cargo feature flag.
+/// A great way to separate them is to put integration tests behind a cargo feature flag. The code
+/// that only the integration tests use (like `serve_one` below) is still compiled without the
+/// feature, so it can't rot.
 /// - Unit tests only: `cargo test --package chapter_11 --lib integ_discussion`
 /// - All tests including integ:
 ///   `cargo test --package chapter_11 --lib integ_discussion --features integ`
@@ -305,6 +365,25 @@ mod output {
 /// (See the `Cargo.toml` for the feature definition)
 ///
 mod integ_discussion {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    /// The logic of our pretend server. It's pure, so it can be unit tested without a network.
+    fn greeting_reply(request: &str) -> String {
+        format!("Hello, {}!\n", request.trim())
+    }
+
+    /// Accepts one connection, reads one line and replies with a greeting.
+    fn serve_one(listener: TcpListener) -> std::io::Result<()> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+        let mut stream = stream;
+        stream.write_all(greeting_reply(&request).as_bytes())
+    }
+
     #[test]
     #[cfg(feature = "integ")]
     fn integrate_with_postgres() {
@@ -314,9 +393,31 @@ mod integ_discussion {
         // check the inserted rows
     }
 
+    /// An integration test that we can actually run: it uses a real TCP socket instead of docker,
+    /// but it still needs things from the host (a network stack, a free port, threads), so it goes
+    /// behind the feature flag too. Run it with:
+    /// `cargo test --package chapter-11 --lib integ_discussion --features integ`
+    #[test]
+    #[cfg(feature = "integ")]
+    fn integrate_with_a_tcp_server() {
+        // Port 0 asks the OS for any free port, so tests running in parallel don't collide.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve_one(listener));
+
+        let mut client = TcpStream::connect(address).unwrap();
+        client.write_all(b"Ferris\n").unwrap();
+        let mut reply = String::new();
+        BufReader::new(client).read_line(&mut reply).unwrap();
+
+        assert_eq!("Hello, Ferris!\n", reply);
+        server.join().unwrap().unwrap();
+    }
+
     #[test]
     fn unit_test() {
         // check some input parsing logic
+        assert_eq!("Hello, Ferris!\n", greeting_reply("  Ferris\r\n"));
     }
 }
 
@@ -330,26 +431,162 @@ mod integ_discussion {
 /// Pull that out of your function and make it pure.
 ///
 mod pure_functions {
+    use std::fmt::{Display, Formatter};
     use std::io::Read;
     use std::path::Path;
 
-    struct Config;
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Config {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug)]
+    enum ConfigError {
+        /// The file couldn't be read. Only `load_config` can return this.
+        Io(std::io::Error),
+        /// A line that isn't `key = value`. Line numbers start at 1.
+        Malformed {
+            line: usize,
+        },
+        UnknownKey {
+            line: usize,
+            key: String,
+        },
+        InvalidPort {
+            line: usize,
+            value: String,
+        },
+        MissingKey(&'static str),
+    }
+
+    impl Display for ConfigError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ConfigError::Io(e) => write!(f, "unable to read the config: {}", e),
+                ConfigError::Malformed { line } => {
+                    write!(f, "line {}: expected 'key = value'", line)
+                }
+                ConfigError::UnknownKey { line, key } => {
+                    write!(f, "line {}: unknown key '{}'", line, key)
+                }
+                ConfigError::InvalidPort { line, value } => {
+                    write!(f, "line {}: '{}' is not a valid port", line, value)
+                }
+                ConfigError::MissingKey(key) => write!(f, "'{}' is required", key),
+            }
+        }
+    }
+
+    impl std::error::Error for ConfigError {}
 
     /// BAD: this is hard to test
     fn read_config_from_etc() -> Config {
-        std::fs::read_to_string("/etc/my.conf").unwrap();
-        // do the parsing
-        Config {}
+        let contents = std::fs::read_to_string("/etc/my.conf").unwrap();
+        parse_config(&contents).unwrap()
     }
 
     /// GOOD: now you can test the function
     fn read_config<P: AsRef<Path>>(path: P) -> Config {
-        std::fs::read_to_string(path.as_ref()).unwrap();
-        // do the parsing
-        Config {}
+        let contents = std::fs::read_to_string(path.as_ref()).unwrap();
+        parse_config(&contents).unwrap()
+    }
+
+    /// BETTER: no filesystem needed, but the tests still have to make a reader.
+    fn read_config_pure<R: Read>(mut conf: R) -> Result<Config, ConfigError> {
+        let mut contents = String::new();
+        conf.read_to_string(&mut contents)
+            .map_err(ConfigError::Io)?;
+        parse_config(&contents)
+    }
+
+    /// BEST: all of the logic is in a pure function. Same input, same output, no IO. The tests are
+    /// just strings, and every error case can be tested without setting anything up.
+    ///
+    /// The format is `key = value` lines, blank lines are ignored, and both `host` and `port` are
+    /// required.
+    fn parse_config(contents: &str) -> Result<Config, ConfigError> {
+        let mut host = None;
+        let mut port = None;
+
+        for (i, text) in contents.lines().enumerate() {
+            let line = i + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = text
+                .split_once('=')
+                .ok_or(ConfigError::Malformed { line })?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "host" => host = Some(value.to_string()),
+                "port" => {
+                    port = Some(value.parse().map_err(|_| ConfigError::InvalidPort {
+                        line,
+                        value: value.to_string(),
+                    })?)
+                }
+                _ => {
+                    return Err(ConfigError::UnknownKey {
+                        line,
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(Config {
+            host: host.ok_or(ConfigError::MissingKey("host"))?,
+            port: port.ok_or(ConfigError::MissingKey("port"))?,
+        })
+    }
+
+    /// The IO is pushed out to the edge in a function so thin that there is hardly anything to
+    /// test: read the file, hand the contents to the pure function.
+    fn load_config<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let contents = std::fs::read_to_string(path.as_ref()).map_err(ConfigError::Io)?;
+        parse_config(&contents)
     }
 
-    fn read_config_pure<R: Read>(conf: R) -> Config {
-        todo!()
+    #[test]
+    fn parse_valid() {
+        let config = parse_config("host = localhost\n\nport = 8080\n").unwrap();
+        assert_eq!(
+            Config {
+                host: "localhost".to_string(),
+                port: 8080
+            },
+            config
+        );
+    }
+
+    #[test]
+    fn parse_malformed_line() {
+        let err = parse_config("host = localhost\nport 8080").unwrap_err();
+        assert!(matches!(err, ConfigError::Malformed { line: 2 }));
+    }
+
+    #[test]
+    fn parse_unknown_key() {
+        let err = parse_config("hots = localhost").unwrap_err();
+        assert_eq!("line 1: unknown key 'hots'", err.to_string());
+    }
+
+    #[test]
+    fn parse_invalid_port() {
+        let err = parse_config("host = localhost\nport = 99999").unwrap_err();
+        assert_eq!("line 2: '99999' is not a valid port", err.to_string());
+    }
+
+    #[test]
+    fn parse_missing_key() {
+        let err = parse_config("host = localhost").unwrap_err();
+        assert!(matches!(err, ConfigError::MissingKey("port")));
+    }
+
+    #[test]
+    fn read_config_pure_from_bytes() {
+        let config = read_config_pure("host = example.com\nport = 443".as_bytes()).unwrap();
+        assert_eq!(443, config.port);
     }
 }
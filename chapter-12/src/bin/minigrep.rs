@@ -0,0 +1,21 @@
+use chapter_12::{config_from_env_and_iter, run};
+use std::{env, process};
+
+/// The finished `minigrep` from the end of the chapter, using the code in `lib.rs` which has grown
+/// some features that the book does not have.
+///
+/// `cargo run --bin minigrep -- -n -i to poem.txt`
+///
+/// `MINIGREP_IGNORE_CASE=true`, or the book's `IGNORE_CASE=1`, makes every search case-insensitive
+/// unless `--no-ignore-case` is given, see `config_from_env_and_args`.
+fn main() {
+    let config = config_from_env_and_iter(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
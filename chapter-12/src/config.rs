@@ -0,0 +1,574 @@
+use std::env;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The book's `Config::build` returns a `&'static str` as its error. That can't tell the user which
+/// flag was wrong, so we use a proper error type instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The query and/or the file path were missing.
+    NotEnoughArguments,
+    /// An argument started with `-` but is not one of the flags we know about.
+    UnknownFlag(String),
+    /// `ConfigBuilder::build` was called without setting a query.
+    MissingQuery,
+    /// `ConfigBuilder::build` was called without setting a file path.
+    MissingFilePath,
+    /// An environment variable was set to something that isn't a boolean.
+    InvalidEnvVar { name: String, value: String },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::NotEnoughArguments => write!(f, "not enough arguments"),
+            ConfigError::UnknownFlag(flag) => write!(f, "unknown flag '{flag}'"),
+            ConfigError::MissingQuery => write!(f, "a query is required"),
+            ConfigError::MissingFilePath => write!(f, "a file path is required"),
+            ConfigError::InvalidEnvVar { name, value } => {
+                write!(f, "{name} must be true or false, not '{value}'")
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub query: String,
+    pub file_path: String,
+    /// `-i` or `--ignore-case`. Can also be turned on with the `IGNORE_CASE` environment variable,
+    /// or with `MINIGREP_IGNORE_CASE` (see `config_from_env_and_args`). `--no-ignore-case` turns it
+    /// back off.
+    pub ignore_case: bool,
+    /// `-n` or `--line-number`: prefix each matching line with its line number.
+    pub line_number: bool,
+    /// `-c` or `--count`: print the number of matching lines instead of the lines.
+    pub count: bool,
+    /// `--json`: print the matches, with their line numbers, as a JSON array. With `-r` there is
+    /// one array for all of the files, and each match has its path too.
+    pub json: bool,
+    /// `--color`: highlight the matches in red. Ignored when the output is not a terminal. With
+    /// `-i` the matches are found ignoring case, and with `-g` the whole line is the match.
+    pub color: bool,
+    /// `-g` or `--glob`: treat the query as a glob pattern that must match the whole line. Glob
+    /// matching is always case-sensitive.
+    pub glob: bool,
+    /// `-r` or `--recursive`: `file_path` is a directory, and every file under it is searched.
+    pub recursive: bool,
+}
+
+impl Config {
+    /// Starts a `ConfigBuilder`, for building a `Config` in code instead of from arguments.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Flags can be mixed in with the positional arguments, e.g. `minigrep -i to -n poem.txt`. The
+    /// positional-only form from the book, `minigrep to poem.txt`, still works. Anything after `--`
+    /// is treated as positional so that you can search for something like `-n`.
+    pub fn build(args: &[String]) -> Result<Config, ConfigError> {
+        Config::build_with_defaults(args.iter().cloned(), Config::unparsed())
+    }
+
+    /// Chapter 13 improves `build` by taking an iterator instead of a slice. Since we own the
+    /// iterator we own the `String`s that come out of it, so we can move them into the `Config`
+    /// instead of cloning them. `std::env::args()` can be passed in directly.
+    pub fn build_from_iter(args: impl Iterator<Item = String>) -> Result<Config, ConfigError> {
+        Config::build_with_defaults(args, Config::unparsed())
+    }
+
+    /// Fills in `config` from `args`, keeping whatever it already has for any flag that isn't
+    /// given. Every way of building a `Config` from arguments ends up here, so they all parse the
+    /// arguments the same way. The slice versions clone the `String`s to get an iterator.
+    fn build_with_defaults(
+        mut args: impl Iterator<Item = String>,
+        mut config: Config,
+    ) -> Result<Config, ConfigError> {
+        let mut query = None;
+        let mut file_path = None;
+        let mut only_positional = false;
+
+        // Skip the program name.
+        args.next();
+
+        for arg in args {
+            if only_positional || !is_flag(&arg) {
+                // Like the book, we ignore any extra positional arguments.
+                if query.is_none() {
+                    query = Some(arg);
+                } else if file_path.is_none() {
+                    file_path = Some(arg);
+                }
+            } else if arg == "--" {
+                only_positional = true;
+            } else {
+                config.set_flag(&arg)?;
+            }
+        }
+
+        match (query, file_path) {
+            (Some(query), Some(file_path)) => {
+                config.query = query;
+                config.file_path = file_path;
+                Ok(config)
+            }
+            _ => Err(ConfigError::NotEnoughArguments),
+        }
+    }
+
+    /// A `Config` with the default flags and empty positional fields, ready to be filled in.
+    fn unparsed() -> Config {
+        Config {
+            query: String::new(),
+            file_path: String::new(),
+            ignore_case: env::var("IGNORE_CASE").is_ok(),
+            line_number: false,
+            count: false,
+            json: false,
+            color: false,
+            glob: false,
+            recursive: false,
+        }
+    }
+
+    fn set_flag(&mut self, flag: &str) -> Result<(), ConfigError> {
+        match flag {
+            "-i" | "--ignore-case" => self.ignore_case = true,
+            "--no-ignore-case" => self.ignore_case = false,
+            "-n" | "--line-number" => self.line_number = true,
+            "-c" | "--count" => self.count = true,
+            "--json" => self.json = true,
+            "--color" => self.color = true,
+            "-g" | "--glob" => self.glob = true,
+            "-r" | "--recursive" => self.recursive = true,
+            _ => return Err(ConfigError::UnknownFlag(flag.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// The environment variable that `config_from_env_and_args` and `config_from_env_and_iter` read.
+pub const IGNORE_CASE_VAR: &str = "MINIGREP_IGNORE_CASE";
+
+/// Builds a `Config` from the environment and then from `args`, in this order of precedence:
+///
+/// 1. `-i` or `--no-ignore-case` in `args`. If both are given, the last one wins.
+/// 2. The `MINIGREP_IGNORE_CASE` environment variable, which can be `true`, `false`, `1` or `0`.
+/// 3. The book's `IGNORE_CASE` environment variable, which is on if it is set at all.
+/// 4. `false`.
+///
+/// So the environment variable sets a default for every run, and a flag changes it for one run.
+/// This is how most command line tools work. Unlike `IGNORE_CASE`, `MINIGREP_IGNORE_CASE` is
+/// parsed, so `MINIGREP_IGNORE_CASE=false` means what it says, even if `IGNORE_CASE` is set.
+pub fn config_from_env_and_args(args: &[String]) -> Result<Config, ConfigError> {
+    config_from_env_and_iter(args.iter().cloned())
+}
+
+/// The same as `config_from_env_and_args`, but it takes an iterator like `Config::build_from_iter`
+/// does, so `std::env::args()` can be passed in directly. This is what the `minigrep` binary uses.
+pub fn config_from_env_and_iter(args: impl Iterator<Item = String>) -> Result<Config, ConfigError> {
+    Config::build_with_defaults(args, env_defaults()?)
+}
+
+/// The defaults that the flags in `args` are applied on top of. `Config::unparsed` has already
+/// looked at `IGNORE_CASE`, and `MINIGREP_IGNORE_CASE` overrides that if it is set.
+fn env_defaults() -> Result<Config, ConfigError> {
+    let mut defaults = Config::unparsed();
+    defaults.ignore_case = match env::var(IGNORE_CASE_VAR) {
+        Err(_) => defaults.ignore_case,
+        Ok(value) => parse_bool(&value).ok_or_else(|| ConfigError::InvalidEnvVar {
+            name: IGNORE_CASE_VAR.to_string(),
+            value,
+        })?,
+    };
+    Ok(defaults)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Builds a `Config` one setting at a time, e.g. from a test or another program:
+///
+/// ```
+/// use chapter_12::Config;
+///
+/// let config = Config::builder()
+///     .query("to")
+///     .file_path("poem.txt")
+///     .ignore_case(true)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// That is easier to read than making up an `args` slice for `Config::build`, and a misspelled
+/// setting is a compile error instead of an `UnknownFlag`. Each method takes and returns the
+/// builder by value so that the calls can be chained.
+///
+/// The query and file path are required, so `build` returns an error if either is missing. The
+/// `RequestBuilder` in chapter 10a turns that mistake into a compile error by tracking which
+/// fields have been set in a generic parameter, at the cost of a type for every state.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    query: Option<String>,
+    file_path: Option<String>,
+    ignore_case: bool,
+    line_number: bool,
+    count: bool,
+}
+
+impl ConfigBuilder {
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    pub fn file_path(mut self, file_path: impl Into<String>) -> Self {
+        self.file_path = Some(file_path.into());
+        self
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn line_number(mut self, line_number: bool) -> Self {
+        self.line_number = line_number;
+        self
+    }
+
+    pub fn count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Unlike `Config::build`, this does not look at the `IGNORE_CASE` environment variable. Every
+    /// flag is `false` unless it was set on the builder.
+    pub fn build(self) -> Result<Config, ConfigError> {
+        Ok(Config {
+            query: self.query.ok_or(ConfigError::MissingQuery)?,
+            file_path: self.file_path.ok_or(ConfigError::MissingFilePath)?,
+            ignore_case: self.ignore_case,
+            line_number: self.line_number,
+            count: self.count,
+            json: false,
+            color: false,
+            glob: false,
+            recursive: false,
+        })
+    }
+}
+
+/// A lone `-` is not a flag (grep uses it to mean `stdin`).
+fn is_flag(arg: &str) -> bool {
+    arg.starts_with('-') && arg.len() > 1
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn positional_only() {
+        let config = Config::build(&args(&["prog", "query", "file.txt"])).unwrap();
+        assert_eq!("query", config.query);
+        assert_eq!("file.txt", config.file_path);
+        assert!(!config.line_number);
+        assert!(!config.count);
+    }
+
+    #[test]
+    fn flag_before_positional() {
+        let config = Config::build(&args(&["prog", "-i", "query", "file.txt"])).unwrap();
+        assert!(config.ignore_case);
+        assert!(!config.line_number);
+        assert!(!config.count);
+        assert_eq!("query", config.query);
+        assert_eq!("file.txt", config.file_path);
+    }
+
+    #[test]
+    fn flags_interleaved() {
+        let config = Config::build(&args(&["prog", "--count", "query", "-n", "file.txt"])).unwrap();
+        assert!(config.line_number);
+        assert!(config.count);
+        assert_eq!("query", config.query);
+        assert_eq!("file.txt", config.file_path);
+    }
+
+    #[test]
+    fn double_dash_ends_flags() {
+        let config = Config::build(&args(&["prog", "--", "-n", "file.txt"])).unwrap();
+        assert!(!config.line_number);
+        assert_eq!("-n", config.query);
+    }
+
+    #[test]
+    fn json_flag() {
+        let config = Config::build(&args(&["prog", "query", "file.txt", "--json"])).unwrap();
+        assert!(config.json);
+    }
+
+    #[test]
+    fn color_flag() {
+        let config = Config::build(&args(&["prog", "--color", "query", "file.txt"])).unwrap();
+        assert!(config.color);
+    }
+
+    #[test]
+    fn glob_flag() {
+        let config = Config::build(&args(&["prog", "-g", "t?ll", "file.txt"])).unwrap();
+        assert!(config.glob);
+        assert_eq!("t?ll", config.query);
+    }
+
+    #[test]
+    fn recursive_flag() {
+        let config = Config::build(&args(&["prog", "-r", "to", "src"])).unwrap();
+        assert!(config.recursive);
+        assert_eq!("src", config.file_path);
+    }
+
+    #[test]
+    fn unknown_flag() {
+        let err = Config::build(&args(&["prog", "-x", "query", "file.txt"])).unwrap_err();
+        assert_eq!(ConfigError::UnknownFlag("-x".to_string()), err);
+    }
+
+    #[test]
+    fn build_from_iter_flags_and_positional() {
+        let config =
+            Config::build_from_iter(args(&["prog", "query", "-c", "file.txt"]).into_iter())
+                .unwrap();
+        assert!(config.count);
+        assert_eq!("query", config.query);
+        assert_eq!("file.txt", config.file_path);
+    }
+
+    #[test]
+    fn build_from_iter_takes_ownership() {
+        let args = args(&["prog", "query", "file.txt"]);
+        // If the strings are moved instead of cloned, their heap buffers do not change.
+        let query_ptr = args[1].as_ptr();
+        let file_path_ptr = args[2].as_ptr();
+
+        let config = Config::build_from_iter(args.into_iter()).unwrap();
+        assert_eq!(query_ptr, config.query.as_ptr());
+        assert_eq!(file_path_ptr, config.file_path.as_ptr());
+    }
+
+    #[test]
+    fn build_from_iter_not_enough_arguments() {
+        let err = Config::build_from_iter(args(&["prog", "query"]).into_iter()).unwrap_err();
+        assert_eq!(ConfigError::NotEnoughArguments, err);
+    }
+
+    #[test]
+    fn build_and_build_from_iter_agree() {
+        // Both read `IGNORE_CASE`, so don't let another test change it between the two calls.
+        let _guard = EnvGuard::set(None);
+        for case in [
+            &["prog", "query", "file.txt"][..],
+            &["prog", "-n", "--", "-c", "file.txt", "extra"],
+            &["prog", "--no-ignore-case", "-r", "--json", "query", "dir"],
+            &["prog", "query"],
+            &["prog", "--nope", "query", "file.txt"],
+        ] {
+            let args = args(case);
+            assert_eq!(
+                Config::build(&args),
+                Config::build_from_iter(args.clone().into_iter()),
+                "{:?}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn builder_complete() {
+        let config = Config::builder()
+            .query("to")
+            .file_path("poem.txt")
+            .ignore_case(true)
+            .line_number(true)
+            .build()
+            .unwrap();
+        assert_eq!("to", config.query);
+        assert_eq!("poem.txt", config.file_path);
+        assert!(config.ignore_case);
+        assert!(config.line_number);
+        assert!(!config.count);
+    }
+
+    #[test]
+    fn builder_missing_query() {
+        let err = Config::builder().file_path("poem.txt").build().unwrap_err();
+        assert_eq!(ConfigError::MissingQuery, err);
+    }
+
+    #[test]
+    fn builder_missing_file_path() {
+        let err = Config::builder().query("to").build().unwrap_err();
+        assert_eq!(ConfigError::MissingFilePath, err);
+    }
+
+    #[test]
+    fn builder_flags_default_to_false() {
+        let config = Config::builder()
+            .query("to")
+            .file_path("poem.txt")
+            .build()
+            .unwrap();
+        assert!(!config.ignore_case);
+        assert!(!config.line_number);
+        assert!(!config.count);
+        assert!(!config.json);
+        assert!(!config.color);
+        assert!(!config.glob);
+    }
+
+    #[test]
+    fn no_ignore_case_flag() {
+        let config = Config::build(&args(&["prog", "-i", "--no-ignore-case", "to", "poem.txt"]));
+        assert!(!config.unwrap().ignore_case);
+    }
+
+    /// Environment variables are shared by every thread in the process, and tests run in parallel.
+    /// Each test that touches `MINIGREP_IGNORE_CASE` or `IGNORE_CASE` holds this lock, and the
+    /// variables are put back the way they were when the guard is dropped, even if the test fails.
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    struct EnvGuard {
+        previous: Vec<(&'static str, Option<String>)>,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        /// Sets `MINIGREP_IGNORE_CASE` and unsets `IGNORE_CASE`, which might be set in the shell
+        /// that is running the tests.
+        fn set(value: Option<&str>) -> Self {
+            Self::set_both(value, None)
+        }
+
+        fn set_both(value: Option<&str>, legacy: Option<&str>) -> Self {
+            let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let mut previous = Vec::new();
+            for (name, value) in [(IGNORE_CASE_VAR, value), ("IGNORE_CASE", legacy)] {
+                previous.push((name, env::var(name).ok()));
+                set_var(name, value);
+            }
+            Self {
+                previous,
+                _lock: lock,
+            }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (name, value) in &self.previous {
+                set_var(name, value.as_deref());
+            }
+        }
+    }
+
+    fn set_var(name: &str, value: Option<&str>) {
+        match value {
+            Some(value) => env::set_var(name, value),
+            None => env::remove_var(name),
+        }
+    }
+
+    #[test]
+    fn env_unset_defaults_to_false() {
+        let _guard = EnvGuard::set(None);
+        let config = config_from_env_and_args(&args(&["prog", "to", "poem.txt"])).unwrap();
+        assert!(!config.ignore_case);
+    }
+
+    #[test]
+    fn env_sets_the_default() {
+        let _guard = EnvGuard::set(Some("true"));
+        let config = config_from_env_and_args(&args(&["prog", "to", "poem.txt"])).unwrap();
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn flag_overrides_env_false() {
+        let _guard = EnvGuard::set(Some("0"));
+        let config = config_from_env_and_args(&args(&["prog", "-i", "to", "poem.txt"])).unwrap();
+        assert!(config.ignore_case);
+    }
+
+    #[test]
+    fn flag_overrides_env_true() {
+        let _guard = EnvGuard::set(Some("1"));
+        let args = args(&["prog", "--no-ignore-case", "to", "poem.txt"]);
+        let config = config_from_env_and_args(&args).unwrap();
+        assert!(!config.ignore_case);
+    }
+
+    #[test]
+    fn env_sets_the_default_from_iter() {
+        let _guard = EnvGuard::set(Some("true"));
+        let config = config_from_env_and_iter(args(&["prog", "to", "poem.txt"]).into_iter());
+        assert!(config.unwrap().ignore_case);
+        let args = args(&["prog", "to", "--no-ignore-case", "poem.txt"]);
+        assert!(
+            !config_from_env_and_iter(args.into_iter())
+                .unwrap()
+                .ignore_case
+        );
+    }
+
+    #[test]
+    fn legacy_env_still_works_from_iter() {
+        let _guard = EnvGuard::set_both(None, Some("1"));
+        let config = config_from_env_and_iter(args(&["prog", "to", "poem.txt"]).into_iter());
+        assert!(config.unwrap().ignore_case);
+        let args = args(&["prog", "--no-ignore-case", "to", "poem.txt"]);
+        assert!(
+            !config_from_env_and_iter(args.into_iter())
+                .unwrap()
+                .ignore_case
+        );
+    }
+
+    #[test]
+    fn env_overrides_legacy_env() {
+        let _guard = EnvGuard::set_both(Some("false"), Some("1"));
+        let config = config_from_env_and_iter(args(&["prog", "to", "poem.txt"]).into_iter());
+        assert!(!config.unwrap().ignore_case);
+    }
+
+    #[test]
+    fn env_invalid() {
+        let _guard = EnvGuard::set(Some("sometimes"));
+        let err = config_from_env_and_args(&args(&["prog", "to", "poem.txt"])).unwrap_err();
+        assert_eq!(
+            "MINIGREP_IGNORE_CASE must be true or false, not 'sometimes'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn not_enough_arguments() {
+        let err = Config::build(&args(&["prog", "-i", "query"])).unwrap_err();
+        assert_eq!(ConfigError::NotEnoughArguments, err);
+    }
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads a file that might not be valid UTF-8.
+///
+/// `fs::read_to_string` fails with `InvalidData` if the file has even one byte sequence that isn't
+/// UTF-8, e.g. a log file with some Latin-1 in it. Here we read the raw bytes instead and let
+/// `String::from_utf8_lossy` replace each bad sequence with `U+FFFD` (`�`), the Unicode
+/// replacement character. The rest of the text comes through unchanged, so the search functions
+/// can still find it.
+///
+/// `from_utf8_lossy` returns a `Cow<str>`: if the bytes were valid it borrows them, and if it had
+/// to replace something it allocates a new `String`. We own the bytes, so we can avoid that copy in
+/// the valid case by trying `String::from_utf8` first, which reuses the `Vec`'s buffer.
+///
+/// The replacement is one-way. If the file has to be written back exactly as it was, work with the
+/// bytes instead.
+pub fn read_lossy(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::search;
+
+    #[test]
+    fn invalid_bytes_are_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latin1.txt");
+        // `0xE9` is `é` in Latin-1, but on its own it is not valid UTF-8.
+        fs::write(&path, b"caf\xE9 au lait\nsafe, fast, productive.\n").unwrap();
+
+        assert!(fs::read_to_string(&path).is_err());
+        let contents = read_lossy(&path).unwrap();
+        assert!(contents.contains('\u{FFFD}'));
+        assert!(contents.starts_with("caf\u{FFFD} au lait"));
+        assert_eq!(vec!["safe, fast, productive."], search("duct", &contents));
+    }
+
+    #[test]
+    fn valid_utf8_is_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("utf8.txt");
+        fs::write(&path, "¿Qué pasa?").unwrap();
+        assert_eq!("¿Qué pasa?", read_lossy(&path).unwrap());
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_lossy(&dir.path().join("nope.txt")).is_err());
+    }
+}
@@ -1 +1,225 @@
 #![allow(dead_code, unused_variables, unused_mut, unused_imports)]
+
+//! The book moves `Config`, `run` and the search functions into a `lib.rs` file. The listings in
+//! `src/bin` are snapshots of each step in the chapter. This library is where we keep going after
+//! the chapter ends and add the features that a real grep would have.
+//!
+//! Run it with `cargo run --bin minigrep -- -n to poem.txt`
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+
+pub mod config;
+pub mod input;
+pub mod output;
+pub mod parallel;
+pub mod search;
+pub mod settings;
+pub mod walk;
+
+pub use config::{
+    config_from_env_and_args, config_from_env_and_iter, Config, ConfigBuilder, ConfigError,
+};
+pub use input::read_lossy;
+pub use output::{
+    file_matches_to_json, highlight, highlight_case_insensitive, highlight_line, matches_to_json,
+};
+pub use parallel::{search_files, search_files_parallel};
+pub use search::{
+    count_lines, match_offsets, matches_glob, search, search_case_insensitive, search_glob,
+    search_streaming, with_line_numbers,
+};
+pub use settings::{Settings, SettingsError};
+pub use walk::{collect_files, collect_files_excluding, should_skip};
+
+/// The main program logic.
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    if !config.recursive {
+        let contents = fs::read_to_string(&config.file_path)?;
+        print_matches(&config, &contents, "");
+        return Ok(());
+    }
+
+    if config.json {
+        println!("{}", recursive_json(&config)?);
+        return Ok(());
+    }
+
+    // Like grep, each line is labelled with the file that it came from.
+    for_each_file(&config, |path, contents| {
+        print_matches(&config, contents, &format!("{}:", path.display()));
+    })
+}
+
+/// Calls `f` with the path and contents of every file under `config.file_path`.
+fn for_each_file(config: &Config, mut f: impl FnMut(&Path, &str)) -> Result<(), Box<dyn Error>> {
+    for path in collect_files(Path::new(&config.file_path), true)? {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // A directory tree often has binary files in it (images, build output...). Like grep,
+            // skip them rather than give up on the whole search.
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => continue,
+            Err(e) => return Err(e.into()),
+        };
+        f(&path, &contents);
+    }
+    Ok(())
+}
+
+/// `-r --json` prints one JSON array for the whole search, with the path in each match, instead
+/// of one labelled array per file. Files without any matches don't appear at all.
+fn recursive_json(config: &Config) -> Result<String, Box<dyn Error>> {
+    // The contents of each file are gone once `for_each_file` moves on, so keep owned copies.
+    let mut matches: Vec<(String, usize, String)> = Vec::new();
+    for_each_file(config, |path, contents| {
+        let results = find_matches(config, contents);
+        for (n, line) in with_line_numbers(contents, &results) {
+            matches.push((path.display().to_string(), n, line.to_string()));
+        }
+    })?;
+    let matches: Vec<(&str, usize, &str)> = matches
+        .iter()
+        .map(|(path, n, line)| (path.as_str(), *n, line.as_str()))
+        .collect();
+    Ok(file_matches_to_json(&matches))
+}
+
+/// The lines of `contents` that match, using whichever kind of search `config` asks for.
+fn find_matches<'a>(config: &Config, contents: &'a str) -> Vec<&'a str> {
+    if config.glob {
+        search_glob(&config.query, contents)
+    } else if config.ignore_case {
+        search_case_insensitive(&config.query, contents)
+    } else {
+        search(&config.query, contents)
+    }
+}
+
+/// Highlights the part of `line` that matched, found the same way that `find_matches` found the
+/// line: ignoring case with `-i`, and the whole line with `-g` because a glob matches all of it.
+fn paint(config: &Config, line: &str) -> String {
+    if config.glob {
+        highlight_line(line)
+    } else if config.ignore_case {
+        highlight_case_insensitive(line, &config.query)
+    } else {
+        highlight(line, &config.query)
+    }
+}
+
+/// Searches `contents` and prints the results the way `config` asks for, with `label` in front of
+/// each line.
+fn print_matches(config: &Config, contents: &str, label: &str) {
+    let results = find_matches(config, contents);
+
+    if config.count {
+        println!("{label}{}", results.len());
+        return;
+    }
+
+    // Escape codes are garbage in a file or another program's input, so only color a terminal.
+    let color = config.color && io::stdout().is_terminal();
+    let paint = |line: &str| {
+        if color {
+            paint(config, line)
+        } else {
+            line.to_string()
+        }
+    };
+
+    if config.json {
+        // `run` handles `-r --json` itself, so there is never a label to put in front of this.
+        println!(
+            "{}",
+            matches_to_json(&with_line_numbers(contents, &results))
+        );
+    } else if config.line_number {
+        for (n, line) in with_line_numbers(contents, &results) {
+            println!("{label}{n}:{}", paint(line));
+        }
+    } else {
+        for line in results {
+            println!("{label}{}", paint(line));
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recursive_json_is_one_array_with_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::create_dir(dir.path().join("empty")).unwrap();
+        fs::write(dir.path().join("a.txt"), "to be\nor not\nto be").unwrap();
+        fs::write(dir.path().join("sub/b.txt"), "nothing here").unwrap();
+        fs::write(dir.path().join("sub/c.txt"), "x\ngo to").unwrap();
+
+        let args: Vec<String> = ["minigrep", "-r", "--json", "to"]
+            .iter()
+            .map(|s| s.to_string())
+            .chain([dir.path().display().to_string()])
+            .collect();
+        let config = Config::build(&args).unwrap();
+        let a = dir.path().join("a.txt").display().to_string();
+        let c = dir.path().join("sub/c.txt").display().to_string();
+        assert_eq!(
+            file_matches_to_json(&[(&a, 1, "to be"), (&a, 3, "to be"), (&c, 2, "go to")]),
+            recursive_json(&config).unwrap()
+        );
+    }
+
+    fn config(flags: &[&str]) -> Config {
+        let args: Vec<String> = ["minigrep"]
+            .iter()
+            .chain(flags)
+            .chain(&["poem.txt"])
+            .map(|s| s.to_string())
+            .collect();
+        Config::build(&args).unwrap()
+    }
+
+    #[test]
+    fn paint_highlights_the_exact_query() {
+        let config = config(&["--no-ignore-case", "--color", "to"]);
+        assert_eq!(
+            "To be or not \x1b[31mto\x1b[0m be",
+            paint(&config, "To be or not to be")
+        );
+    }
+
+    #[test]
+    fn paint_ignore_case() {
+        let config = config(&["--color", "-i", "to"]);
+        assert_eq!("\x1b[31mTo\x1b[0m be", paint(&config, "To be"));
+    }
+
+    #[test]
+    fn paint_glob_highlights_the_whole_line() {
+        let config = config(&["--color", "-g", "*duct*"]);
+        assert_eq!(
+            "\x1b[31msafe, fast, productive.\x1b[0m",
+            paint(&config, "safe, fast, productive.")
+        );
+    }
+
+    #[test]
+    fn recursive_json_with_no_matches_is_an_empty_array() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "nothing here").unwrap();
+        let config = Config::builder()
+            .query("to")
+            .file_path(dir.path().display().to_string())
+            .build()
+            .unwrap();
+        assert_eq!("[]", recursive_json(&config).unwrap());
+    }
+}
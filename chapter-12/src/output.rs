@@ -0,0 +1,259 @@
+use std::fmt::Write;
+
+/// Formats the matches as a JSON array of `{"line": N, "text": "..."}` objects so that the output
+/// can be piped into tools like `jq`.
+///
+/// We could get this for free with `serde_json`, but JSON strings are simple enough to escape by
+/// hand and it's a good look at what a serializer has to think about.
+pub fn matches_to_json(matches: &[(usize, &str)]) -> String {
+    let mut json = String::from("[");
+    for (i, (line, text)) in matches.iter().enumerate() {
+        if i > 0 {
+            json.push_str(", ");
+        }
+        // Writing to a `String` can't fail.
+        let _ = write!(json, "{{\"line\": {}, \"text\": ", line);
+        push_json_string(&mut json, text);
+        json.push('}');
+    }
+    json.push(']');
+    json
+}
+
+/// Like `matches_to_json`, but for matches from more than one file, as `minigrep -r --json`
+/// prints them: `{"path": "...", "line": N, "text": "..."}`. It's still a single JSON array, so the
+/// whole output can be parsed in one go. Putting `path: ` in front of an array for each file, like
+/// the plain text output does, would not be JSON at all.
+pub fn file_matches_to_json(matches: &[(&str, usize, &str)]) -> String {
+    let mut json = String::from("[");
+    for (i, (path, line, text)) in matches.iter().enumerate() {
+        if i > 0 {
+            json.push_str(", ");
+        }
+        json.push_str("{\"path\": ");
+        push_json_string(&mut json, path);
+        let _ = write!(json, ", \"line\": {}, \"text\": ", line);
+        push_json_string(&mut json, text);
+        json.push('}');
+    }
+    json.push(']');
+    json
+}
+
+/// Appends `s` to `json` as a quoted JSON string. JSON requires quotes, backslashes and control
+/// characters (anything below U+0020) to be escaped. Everything else, including non-ASCII text, can
+/// go in as-is because JSON is UTF-8.
+fn push_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < '\u{20}' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// The ANSI escape code that turns the terminal's text red.
+const RED: &str = "\x1b[31m";
+/// The ANSI escape code that goes back to the terminal's normal colors.
+const RESET: &str = "\x1b[0m";
+
+/// Wraps every occurrence of `query` in `line` with the ANSI codes for red text. `str::replace`
+/// scans from left to right and skips past each match, so overlapping matches (like `aa` in `aaa`)
+/// are only highlighted once.
+///
+/// Only exact (case-sensitive) occurrences are highlighted. An empty query would match between
+/// every character, so the line is returned as-is.
+pub fn highlight(line: &str, query: &str) -> String {
+    if query.is_empty() {
+        return line.to_string();
+    }
+    line.replace(query, &format!("{RED}{query}{RESET}"))
+}
+
+/// Like `highlight`, but for `-i`: highlights every part of `line` that matches `query` when both
+/// are lowercased, the same test that `search_case_insensitive` uses. The matches keep their own
+/// case, so `to` in `To be` highlights `To`.
+///
+/// We can't just lowercase the line and look for the query in that, because the offsets might not
+/// line up with the original: a few characters change length when they are lowercased (`İ` is two
+/// bytes, and its lowercase form is three). So at each character we lowercase just enough of the
+/// line to compare with the query. If the end of a match falls inside a character's lowercase
+/// form, the whole character is highlighted.
+pub fn highlight_case_insensitive(line: &str, query: &str) -> String {
+    if query.is_empty() {
+        return line.to_string();
+    }
+    let query = query.to_lowercase();
+    let mut highlighted = String::with_capacity(line.len());
+    let mut start = 0;
+    while let Some(c) = line[start..].chars().next() {
+        match match_len_ignoring_case(&line[start..], &query) {
+            Some(len) => {
+                let _ = write!(highlighted, "{RED}{}{RESET}", &line[start..start + len]);
+                start += len;
+            }
+            None => {
+                highlighted.push(c);
+                start += c.len_utf8();
+            }
+        }
+    }
+    highlighted
+}
+
+/// If the start of `text` matches `lower_query` when lowercased, returns how many bytes of `text`
+/// that takes.
+fn match_len_ignoring_case(text: &str, lower_query: &str) -> Option<usize> {
+    let mut lowered = String::new();
+    for (i, c) in text.char_indices() {
+        lowered.extend(c.to_lowercase());
+        if lowered.starts_with(lower_query) {
+            return Some(i + c.len_utf8());
+        }
+        if !lower_query.starts_with(&lowered) {
+            return None;
+        }
+    }
+    None
+}
+
+/// For `-g`: a glob has to match the whole line, so the whole line is the match.
+pub fn highlight_line(line: &str) -> String {
+    format!("{RED}{line}{RESET}")
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_matches() {
+        assert_eq!("[]", matches_to_json(&[]));
+    }
+
+    #[test]
+    fn plain_lines() {
+        assert_eq!(
+            r#"[{"line": 1, "text": "Rust:"}, {"line": 4, "text": "Trust me."}]"#,
+            matches_to_json(&[(1, "Rust:"), (4, "Trust me.")])
+        );
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            r#"[{"line": 2, "text": "she said \"hi\""}]"#,
+            matches_to_json(&[(2, r#"she said "hi""#)])
+        );
+    }
+
+    #[test]
+    fn backslashes() {
+        assert_eq!(
+            r#"[{"line": 3, "text": "C:\\Users\\me"}]"#,
+            matches_to_json(&[(3, r"C:\Users\me")])
+        );
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(
+            r#"[{"line": 1, "text": "a\tb\u0007"}]"#,
+            matches_to_json(&[(1, "a\tb\u{7}")])
+        );
+    }
+
+    #[test]
+    fn non_ascii_is_not_escaped() {
+        assert_eq!(
+            r#"[{"line": 1, "text": "¿Qué pasa?"}]"#,
+            matches_to_json(&[(1, "¿Qué pasa?")])
+        );
+    }
+
+    #[test]
+    fn file_matches() {
+        assert_eq!(
+            r#"[{"path": "a.txt", "line": 1, "text": "to be"}, {"path": "sub\\b \"c\".txt", "line": 3, "text": "not to"}]"#,
+            file_matches_to_json(&[("a.txt", 1, "to be"), (r#"sub\b "c".txt"#, 3, "not to")])
+        );
+        assert_eq!("[]", file_matches_to_json(&[]));
+    }
+
+    #[test]
+    fn highlight_one_match() {
+        assert_eq!("Trust \x1b[31mme\x1b[0m.", highlight("Trust me.", "me"));
+    }
+
+    #[test]
+    fn highlight_every_match() {
+        assert_eq!(
+            "\x1b[31mto\x1b[0m be or not \x1b[31mto\x1b[0m be",
+            highlight("to be or not to be", "to")
+        );
+    }
+
+    #[test]
+    fn highlight_does_not_overlap() {
+        assert_eq!("\x1b[31maa\x1b[0ma", highlight("aaa", "aa"));
+    }
+
+    #[test]
+    fn highlight_no_match() {
+        assert_eq!("Pick three.", highlight("Pick three.", "duct"));
+    }
+
+    #[test]
+    fn highlight_empty_query() {
+        assert_eq!("Pick three.", highlight("Pick three.", ""));
+    }
+
+    #[test]
+    fn highlight_case_insensitive_keeps_the_line_case() {
+        assert_eq!(
+            "\x1b[31mTo\x1b[0m be",
+            highlight_case_insensitive("To be", "to")
+        );
+        assert_eq!(
+            "\x1b[31mTO\x1b[0m be or not \x1b[31mto\x1b[0m be",
+            highlight_case_insensitive("TO be or not to be", "tO")
+        );
+    }
+
+    #[test]
+    fn highlight_case_insensitive_no_match() {
+        assert_eq!(
+            "Pick three.",
+            highlight_case_insensitive("Pick three.", "duct")
+        );
+        assert_eq!("Pick three.", highlight_case_insensitive("Pick three.", ""));
+    }
+
+    #[test]
+    fn highlight_case_insensitive_non_ascii() {
+        assert_eq!(
+            "¿\x1b[31mQUÉ\x1b[0m pasa?",
+            highlight_case_insensitive("¿QUÉ pasa?", "qué")
+        );
+        // `İ` lowercases to `i` and a combining dot, so the whole `İ` is highlighted for `i`.
+        assert_eq!("\x1b[31mİ\x1b[0mx", highlight_case_insensitive("İx", "i"));
+    }
+
+    #[test]
+    fn highlight_whole_line() {
+        assert_eq!("\x1b[31mproductive.\x1b[0m", highlight_line("productive."));
+    }
+}
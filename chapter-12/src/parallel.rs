@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use crate::search::search;
+
+/// Searches each file in turn and returns the matching lines from each, sorted by path. Files that
+/// can't be read as text (missing, binary, no permission...) are left out.
+pub fn search_files(query: &str, files: &[PathBuf]) -> Vec<(PathBuf, Vec<String>)> {
+    let mut results: Vec<_> = files
+        .iter()
+        .filter_map(|path| search_file(query, path))
+        .collect();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+/// The same as `search_files`, but the files are split between `threads` worker threads.
+///
+/// This uses `thread::scope` (chapter 16 only shows `thread::spawn`). A `spawn`ed thread might
+/// outlive the function that started it, so everything it uses has to be moved in or put in an
+/// `Arc`. A scoped thread is guaranteed to be joined before `scope` returns, so it can borrow
+/// `query` and `files` directly.
+///
+/// Each thread gets a slice of the files and returns its own `Vec` of results. Nothing is shared
+/// while the threads run, so there is no `Mutex`. The threads finish in whatever order they like,
+/// so the results are sorted by path at the end to make the output the same every time.
+///
+/// Searching files is mostly waiting on the disk, so more threads than CPUs can still help, but
+/// not forever. `threads` is clamped to at least one and at most one per file.
+pub fn search_files_parallel(
+    query: &str,
+    files: &[PathBuf],
+    threads: usize,
+) -> Vec<(PathBuf, Vec<String>)> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    let threads = threads.clamp(1, files.len());
+    let chunk_size = files.len().div_ceil(threads);
+
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|path| search_file(query, path))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+fn search_file(query: &str, path: &Path) -> Option<(PathBuf, Vec<String>)> {
+    let contents = fs::read_to_string(path).ok()?;
+    let lines = search(query, &contents)
+        .into_iter()
+        .map(String::from)
+        .collect();
+    Some((path.to_path_buf(), lines))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Writes `count` files, each with a couple of lines that mention its number, and returns them
+    /// in reverse order so that the sorting has something to do.
+    fn files(count: usize) -> (TempDir, Vec<PathBuf>) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths: Vec<_> = (0..count)
+            .map(|i| {
+                let path = dir.path().join(format!("file_{:02}.txt", i));
+                fs::write(
+                    &path,
+                    format!("line one of {i}\nnothing here\nline two of {i}\n"),
+                )
+                .unwrap();
+                path
+            })
+            .collect();
+        paths.reverse();
+        (dir, paths)
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let (_dir, files) = files(10);
+        let sequential = search_files("line", &files);
+        assert_eq!(10, sequential.len());
+        for threads in [1, 3, 4, 10, 50] {
+            assert_eq!(sequential, search_files_parallel("line", &files, threads));
+        }
+    }
+
+    #[test]
+    fn results_are_sorted_by_path() {
+        let (_dir, files) = files(5);
+        let results = search_files_parallel("one", &files, 2);
+        let paths: Vec<_> = results.iter().map(|(path, _)| path.clone()).collect();
+        let mut sorted = paths.clone();
+        sorted.sort();
+        assert_eq!(sorted, paths);
+        assert_eq!(vec!["line one of 0".to_string()], results[0].1);
+    }
+
+    #[test]
+    fn zero_threads_still_searches() {
+        let (_dir, files) = files(3);
+        assert_eq!(3, search_files_parallel("two", &files, 0).len());
+    }
+
+    #[test]
+    fn no_files() {
+        assert!(search_files_parallel("line", &[], 4).is_empty());
+    }
+
+    #[test]
+    fn unreadable_files_are_left_out() {
+        let (dir, mut files) = files(2);
+        files.push(dir.path().join("missing.txt"));
+        assert_eq!(2, search_files_parallel("line", &files, 2).len());
+    }
+}
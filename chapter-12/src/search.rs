@@ -0,0 +1,410 @@
+use std::io::{self, BufRead};
+
+/// The search function.
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let mut results = Vec::new();
+
+    for line in contents.lines() {
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+
+    results
+}
+
+/// Search case insensitively.
+///
+/// We use `str::to_lowercase`, which knows about Unicode, instead of `str::to_ascii_lowercase`,
+/// which only changes `A` through `Z`. Remember from chapter 8 that a `String` is UTF-8, so a query
+/// like `QUÉ` would not match `qué` if only the ASCII letters were lowered.
+///
+/// This is not free. `to_lowercase` allocates a new `String` for the query and for *every* line,
+/// and a lowercased string can even be a different length than the original. If that matters, the
+/// `unicase` crate can compare strings case-insensitively without allocating. Full Unicode case
+/// folding (e.g. `ß` vs `SS`) is even more involved and is not handled by `to_lowercase` either.
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+    let mut results = Vec::new();
+
+    for line in contents.lines() {
+        if line.to_lowercase().contains(&query) {
+            results.push(line);
+        }
+    }
+
+    results
+}
+
+/// Searches a reader line by line so that the whole file never needs to be in memory at once. Only
+/// the matching lines are kept.
+///
+/// The tradeoff is that we can no longer return `&str` slices like `search` does. The slices in
+/// `search` borrow from `contents`, which the caller holds on to. Here each line is read into a
+/// buffer that is thrown away (or reused) before the next line is read, so there is nothing for a
+/// slice to borrow from and we must return owned `String`s. That's one allocation per match, but
+/// memory use is bounded by the size of the matches instead of the size of the file.
+///
+/// Note that `lines()` strips the `\n` (or `\r\n`) and will return an error if a line is not
+/// valid UTF-8.
+pub fn search_streaming<R: BufRead>(query: &str, reader: R) -> io::Result<Vec<String>> {
+    let mut results = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Counts the lines in `reader` without ever holding more than one buffer of it in memory.
+///
+/// `reader.lines().count()` would also work, but it allocates a `String` for every line, and a
+/// single huge line (a minified file, say) would have to fit in memory all at once. Instead we ask
+/// the `BufRead` for whatever it has in its buffer with `fill_buf`, count the `\n` bytes in it, and
+/// tell it we're done with those bytes with `consume`. The buffer is reused, so memory use doesn't
+/// depend on the size of the file or the length of its lines. Counting bytes also means we don't
+/// care whether the file is valid UTF-8.
+///
+/// A line is counted the way `str::lines` counts it: the last line counts even if it doesn't end
+/// with `\n`, and an empty input has no lines.
+pub fn count_lines<R: BufRead>(mut reader: R) -> io::Result<usize> {
+    let mut count = 0;
+    let mut last_byte = None;
+    loop {
+        let buffer = match reader.fill_buf() {
+            Ok(buffer) => buffer,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        // An empty buffer means the end of the input.
+        if buffer.is_empty() {
+            break;
+        }
+        count += buffer.iter().filter(|&&b| b == b'\n').count();
+        last_byte = buffer.last().copied();
+        let length = buffer.len();
+        reader.consume(length);
+    }
+    // Text after the last `\n` is a line too.
+    if last_byte.is_some_and(|b| b != b'\n') {
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Searches with a glob pattern instead of plain text. Like a shell glob, the pattern has to match
+/// the *whole* line, so use `*duct*` to find `duct` anywhere in a line. See `matches_glob`.
+pub fn search_glob<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| matches_glob(query, line))
+        .collect()
+}
+
+/// Returns `true` if `line` matches the glob pattern `query`, where `*` matches any run of
+/// characters (including none) and `?` matches exactly one character. Everything else must match
+/// exactly, so a pattern with no wildcards only matches an identical line.
+///
+/// This is a dynamic programming solution. `matched[j]` answers the question "does the part of the
+/// pattern we have looked at so far match the first `j` characters of the line?" We fill it in
+/// one pattern character at a time:
+/// - A literal or `?` can only extend a match that was one character shorter.
+/// - A `*` matches if the previous row matched here (the `*` is empty) or if the `*` already
+///   matched one character shorter (the `*` eats one more character).
+///
+/// That's `O(pattern * line)` time and `O(line)` memory. The regex crate would do this for us, but
+/// it's a good example of how a small matcher works.
+pub fn matches_glob(query: &str, line: &str) -> bool {
+    let line: Vec<char> = line.chars().collect();
+
+    // Before looking at any of the pattern, we have only matched the empty prefix of the line.
+    let mut matched = vec![false; line.len() + 1];
+    matched[0] = true;
+
+    for p in query.chars() {
+        let mut next = vec![false; line.len() + 1];
+        if p == '*' {
+            next[0] = matched[0];
+            for j in 1..=line.len() {
+                next[j] = matched[j] || next[j - 1];
+            }
+        } else {
+            for j in 1..=line.len() {
+                next[j] = matched[j - 1] && (p == '?' || p == line[j - 1]);
+            }
+        }
+        matched = next;
+    }
+
+    matched[line.len()]
+}
+
+/// Returns the byte offset into `contents` of every place that `query` appears. Unlike the other
+/// searches this doesn't care about lines, and it doesn't return the text, only where it is.
+/// `&contents[offset..offset + query.len()]` gets the match back.
+///
+/// Overlapping matches are counted, so `"aa"` is found 3 times in `"aaaa"`. `str::match_indices`
+/// would be the obvious tool, but it skips past each match before looking for the next one, so it
+/// only finds `"aa"` twice. Instead we start looking again one character after the start of each
+/// match. We step by a whole character, not a byte, because slicing a `str` in the middle of a
+/// multi-byte character panics.
+///
+/// An empty query would match between every character, which isn't useful, so it matches nothing.
+pub fn match_offsets(query: &str, contents: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    if query.is_empty() {
+        return offsets;
+    }
+
+    let mut start = 0;
+    while let Some(found) = contents[start..].find(query) {
+        let offset = start + found;
+        offsets.push(offset);
+        // `query` is not empty, so there is at least one character here.
+        let first_char_len = contents[offset..].chars().next().unwrap().len_utf8();
+        start = offset + first_char_len;
+    }
+    offsets
+}
+
+/// Pairs each line found by a search with its line number (starting at 1). This works because the
+/// search functions return slices that point into `contents`, so we can walk `contents` once and
+/// compare pointers instead of comparing the text (which could match a duplicate line).
+pub fn with_line_numbers<'a>(contents: &'a str, results: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let mut results = results.iter().copied().peekable();
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| match results.peek() {
+            Some(found) if std::ptr::eq(*found, line) => {
+                results.next();
+                Some((i + 1, line))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, contents)
+        );
+    }
+
+    #[test]
+    fn case_insensitive_unicode() {
+        let query = "QUÉ";
+        let contents = "\
+¿Qué pasa?
+Que sera, sera.
+ASCII only.";
+
+        assert_eq!(vec!["¿Qué pasa?"], search_case_insensitive(query, contents));
+    }
+
+    #[test]
+    fn streaming() {
+        let contents = Cursor::new(
+            "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+",
+        );
+
+        assert_eq!(
+            vec!["safe, fast, productive."],
+            search_streaming("duct", contents).unwrap()
+        );
+    }
+
+    #[test]
+    fn streaming_last_line_without_newline() {
+        let contents = Cursor::new("Pick three.\nsafe, fast, productive.");
+
+        assert_eq!(
+            vec!["safe, fast, productive."],
+            search_streaming("duct", contents).unwrap()
+        );
+    }
+
+    #[test]
+    fn offsets_of_two_matches() {
+        let contents = "Rust:\nTrust me.";
+        assert_eq!(vec![1, 8], match_offsets("ust", contents));
+    }
+
+    #[test]
+    fn offsets_overlapping() {
+        assert_eq!(vec![0, 1, 2], match_offsets("aa", "aaaa"));
+        // `match_indices` does not count overlapping matches.
+        assert_eq!(2, "aaaa".match_indices("aa").count());
+    }
+
+    #[test]
+    fn offsets_are_bytes() {
+        // `é` is two bytes, so the second `qué` starts at byte 4, not character 3.
+        let contents = "quéquéqué";
+        assert_eq!(vec![0, 4, 8], match_offsets("qué", contents));
+        assert_eq!("qué", &contents[4..4 + "qué".len()]);
+    }
+
+    #[test]
+    fn offsets_empty_query() {
+        assert!(match_offsets("", "anything").is_empty());
+    }
+
+    #[test]
+    fn offsets_no_match() {
+        assert!(match_offsets("xyz", "anything").is_empty());
+    }
+
+    #[test]
+    fn count_lines_empty() {
+        assert_eq!(0, count_lines(Cursor::new("")).unwrap());
+    }
+
+    #[test]
+    fn count_lines_no_trailing_newline() {
+        assert_eq!(3, count_lines(Cursor::new("one\ntwo\nthree")).unwrap());
+    }
+
+    #[test]
+    fn count_lines_trailing_newline() {
+        assert_eq!(3, count_lines(Cursor::new("one\ntwo\nthree\n")).unwrap());
+    }
+
+    #[test]
+    fn count_lines_blank_lines() {
+        assert_eq!(3, count_lines(Cursor::new("\n\n\n")).unwrap());
+        assert_eq!(1, count_lines(Cursor::new("no newline")).unwrap());
+    }
+
+    #[test]
+    fn count_lines_small_buffer() {
+        // A 4 byte buffer forces many `fill_buf` calls and lines that span buffers.
+        let contents = "Rust:\nsafe, fast, productive.\nPick three.\nDuct tape.";
+        let reader = io::BufReader::with_capacity(4, contents.as_bytes());
+        assert_eq!(contents.lines().count(), count_lines(reader).unwrap());
+    }
+
+    #[test]
+    fn count_lines_not_utf8() {
+        let bytes: &[u8] = &[0xff, b'\n', 0xfe];
+        assert_eq!(2, count_lines(bytes).unwrap());
+    }
+
+    #[test]
+    fn line_numbers() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+        let results = search("st", contents);
+
+        assert_eq!(
+            vec![
+                (1, "Rust:"),
+                (2, "safe, fast, productive."),
+                (4, "Trust me.")
+            ],
+            with_line_numbers(contents, &results)
+        );
+    }
+
+    #[test]
+    fn glob_star_in_the_middle() {
+        assert!(matches_glob("pr*ductive", "productive"));
+        assert!(matches_glob("pr*ductive", "prductive"));
+        assert!(!matches_glob("pr*ductive", "productive."));
+    }
+
+    #[test]
+    fn glob_question_mark() {
+        assert!(matches_glob("t?ll", "tell"));
+        assert!(matches_glob("t?ll", "tall"));
+        assert!(!matches_glob("t?ll", "tll"));
+        assert!(!matches_glob("t?ll", "trill"));
+    }
+
+    #[test]
+    fn glob_leading_star() {
+        assert!(matches_glob("*ductive.", "safe, fast, productive."));
+        assert!(!matches_glob("*ductive", "safe, fast, productive."));
+    }
+
+    #[test]
+    fn glob_trailing_star() {
+        assert!(matches_glob("Pick*", "Pick three."));
+        assert!(!matches_glob("three*", "Pick three."));
+    }
+
+    #[test]
+    fn glob_no_wildcards_is_exact() {
+        assert!(matches_glob("Duct tape.", "Duct tape."));
+        assert!(!matches_glob("Duct", "Duct tape."));
+    }
+
+    #[test]
+    fn glob_empty() {
+        assert!(matches_glob("", ""));
+        assert!(matches_glob("*", ""));
+        assert!(!matches_glob("?", ""));
+        assert!(!matches_glob("", "a"));
+    }
+
+    #[test]
+    fn glob_unicode() {
+        assert!(matches_glob("¿Qu? pasa?", "¿Qué pasa?"));
+    }
+
+    #[test]
+    fn glob_search() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(
+            vec!["safe, fast, productive.", "Duct tape."],
+            search_glob("*uct*", contents)
+        );
+    }
+}
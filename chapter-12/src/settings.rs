@@ -0,0 +1,179 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The same options as `Config`, but read from a settings file instead of the command line. Each
+/// line is `key=value`, e.g.
+///
+/// ```text
+/// query=to
+/// file_path=poem.txt
+/// case_insensitive=true
+/// ```
+///
+/// Blank lines and lines starting with `#` are skipped, and spaces around the key and value are
+/// trimmed. Keys that are left out get their `Default` value.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub query: String,
+    pub file_path: String,
+    pub case_insensitive: bool,
+    pub line_number: bool,
+    pub count: bool,
+}
+
+/// What went wrong, and on which line (starting at 1), so that the user can find it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsError {
+    /// The line has no `=` in it.
+    MissingEquals { line: usize },
+    /// The key is not one of the fields of `Settings`.
+    UnknownKey { line: usize, key: String },
+    /// A `true`/`false` setting had some other value.
+    InvalidBool {
+        line: usize,
+        key: String,
+        value: String,
+    },
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingsError::MissingEquals { line } => {
+                write!(f, "line {line}: expected 'key=value'")
+            }
+            SettingsError::UnknownKey { line, key } => {
+                write!(f, "line {line}: unknown key '{key}'")
+            }
+            SettingsError::InvalidBool { line, key, value } => write!(
+                f,
+                "line {line}: '{key}' must be 'true' or 'false', got '{value}'"
+            ),
+        }
+    }
+}
+
+impl Error for SettingsError {}
+
+/// Implementing `FromStr` means we get `str::parse` for free: `"query=to".parse::<Settings>()`.
+impl FromStr for Settings {
+    type Err = SettingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut settings = Settings::default();
+
+        for (i, text) in s.lines().enumerate() {
+            let line = i + 1;
+            let text = text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            // Only split on the first `=` so that a query can contain one.
+            let (key, value) = text
+                .split_once('=')
+                .ok_or(SettingsError::MissingEquals { line })?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let parse_bool = || {
+                value
+                    .parse::<bool>()
+                    .map_err(|_| SettingsError::InvalidBool {
+                        line,
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+            };
+
+            match key {
+                "query" => settings.query = value.to_string(),
+                "file_path" => settings.file_path = value.to_string(),
+                "case_insensitive" => settings.case_insensitive = parse_bool()?,
+                "line_number" => settings.line_number = parse_bool()?,
+                "count" => settings.count = parse_bool()?,
+                _ => {
+                    return Err(SettingsError::UnknownKey {
+                        line,
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_multi_line() {
+        let settings: Settings = "\
+# search the poem
+query=to
+file_path = poem.txt
+
+case_insensitive=true"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            Settings {
+                query: "to".to_string(),
+                file_path: "poem.txt".to_string(),
+                case_insensitive: true,
+                line_number: false,
+                count: false,
+            },
+            settings
+        );
+    }
+
+    #[test]
+    fn value_containing_equals() {
+        let settings: Settings = "query=a=b".parse().unwrap();
+        assert_eq!("a=b", settings.query);
+    }
+
+    #[test]
+    fn empty_is_default() {
+        assert_eq!(Settings::default(), "".parse().unwrap());
+    }
+
+    #[test]
+    fn unknown_key() {
+        let err = "query=to\ncolour=red".parse::<Settings>().unwrap_err();
+        assert_eq!(
+            SettingsError::UnknownKey {
+                line: 2,
+                key: "colour".to_string()
+            },
+            err
+        );
+        assert_eq!("line 2: unknown key 'colour'", err.to_string());
+    }
+
+    #[test]
+    fn missing_equals() {
+        let err = "query=to\n\nfile_path poem.txt"
+            .parse::<Settings>()
+            .unwrap_err();
+        assert_eq!(SettingsError::MissingEquals { line: 3 }, err);
+    }
+
+    #[test]
+    fn invalid_bool() {
+        let err = "count=yes".parse::<Settings>().unwrap_err();
+        assert_eq!(
+            "line 1: 'count' must be 'true' or 'false', got 'yes'",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::search::matches_glob;
+
+/// Finds the files to search under `root`, sorted by path so that the output is always in the same
+/// order.
+///
+/// - If `root` is a file, it is the only file.
+/// - If `root` is a directory, the files directly inside it are returned, and with `recursive` so
+///   are the files inside every directory below it.
+///
+/// Symlinks to files are included, but symlinks to directories are not followed. A link that
+/// points at one of its own parent directories would otherwise send us around in circles forever.
+/// (Following them safely means remembering every directory we have been in, which is what
+/// `walkdir`'s `follow_links` option does.) Anything else, like a broken link, is skipped.
+pub fn collect_files(root: &Path, recursive: bool) -> io::Result<Vec<PathBuf>> {
+    collect_files_excluding(root, recursive, &[])
+}
+
+/// Like `collect_files`, but leaves out anything that `should_skip` says to skip. When a directory
+/// is skipped we don't look inside it at all, which is the point of excluding something like
+/// `target`.
+///
+/// `root` itself is never skipped: if you ask for a directory by name, you get it.
+pub fn collect_files_excluding(
+    root: &Path,
+    recursive: bool,
+    patterns: &[String],
+) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if fs::metadata(root)?.is_file() {
+        files.push(root.to_path_buf());
+    } else {
+        walk(root, recursive, patterns, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns `true` if the name of the file or directory at `path` matches any of the glob
+/// `patterns`, e.g. `*.tmp` or `target`. This is a small piece of what `.gitignore` does: only the
+/// last part of the path is matched, so `target` skips a directory called `target` anywhere in the
+/// tree, but a pattern with a `/` in it never matches anything.
+///
+/// The patterns use `matches_glob`, so they have to match the whole name, and they are
+/// case-sensitive.
+pub fn should_skip(path: &Path, patterns: &[String]) -> bool {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => return false,
+    };
+    patterns.iter().any(|pattern| matches_glob(pattern, &name))
+}
+
+fn walk(
+    dir: &Path,
+    recursive: bool,
+    patterns: &[String],
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if should_skip(&path, patterns) {
+            continue;
+        }
+        // Unlike `fs::metadata`, `DirEntry::file_type` does not follow symlinks, so a symlink shows
+        // up as a symlink here and not as whatever it points to.
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if recursive {
+                walk(&path, recursive, patterns, files)?;
+            }
+        } else if file_type.is_file() || is_link_to_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_link_to_file(path: &Path) -> bool {
+    fs::metadata(path).map(|m| m.is_file()).unwrap_or(false)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Creates this tree and returns the temp dir (which is deleted when it is dropped):
+    ///
+    /// ```text
+    /// a.txt
+    /// sub/b.txt
+    /// sub/deeper/c.txt
+    /// empty/
+    /// ```
+    fn tree() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sub/deeper")).unwrap();
+        fs::create_dir(dir.path().join("empty")).unwrap();
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        fs::write(dir.path().join("sub/b.txt"), "b").unwrap();
+        fs::write(dir.path().join("sub/deeper/c.txt"), "c").unwrap();
+        dir
+    }
+
+    fn relative(root: &Path, files: &[PathBuf]) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| {
+                f.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recursive_finds_files_at_every_depth() {
+        let dir = tree();
+        let files = collect_files(dir.path(), true).unwrap();
+        assert_eq!(
+            vec!["a.txt", "sub/b.txt", "sub/deeper/c.txt"],
+            relative(dir.path(), &files)
+        );
+    }
+
+    #[test]
+    fn not_recursive_only_finds_top_level_files() {
+        let dir = tree();
+        let files = collect_files(dir.path(), false).unwrap();
+        assert_eq!(vec!["a.txt"], relative(dir.path(), &files));
+    }
+
+    #[test]
+    fn root_can_be_a_file() {
+        let dir = tree();
+        let file = dir.path().join("sub/b.txt");
+        assert_eq!(vec![file.clone()], collect_files(&file, true).unwrap());
+    }
+
+    #[test]
+    fn missing_root_is_an_error() {
+        let dir = tree();
+        assert!(collect_files(&dir.path().join("nope"), true).is_err());
+    }
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn excludes_files_by_glob() {
+        let dir = tree();
+        fs::write(dir.path().join("scratch.tmp"), "").unwrap();
+        fs::write(dir.path().join("sub/deeper/old.tmp"), "").unwrap();
+        let files = collect_files_excluding(dir.path(), true, &patterns(&["*.tmp"])).unwrap();
+        assert_eq!(
+            vec!["a.txt", "sub/b.txt", "sub/deeper/c.txt"],
+            relative(dir.path(), &files)
+        );
+    }
+
+    #[test]
+    fn excludes_directories_by_name() {
+        let dir = tree();
+        fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        fs::write(dir.path().join("target/debug/out.txt"), "").unwrap();
+        fs::create_dir_all(dir.path().join("sub/target")).unwrap();
+        fs::write(dir.path().join("sub/target/out.txt"), "").unwrap();
+        let files =
+            collect_files_excluding(dir.path(), true, &patterns(&["target", "*.tmp"])).unwrap();
+        assert_eq!(
+            vec!["a.txt", "sub/b.txt", "sub/deeper/c.txt"],
+            relative(dir.path(), &files)
+        );
+    }
+
+    #[test]
+    fn root_is_never_skipped() {
+        let dir = tree();
+        let root = dir.path().join("sub");
+        let files = collect_files_excluding(&root, true, &patterns(&["sub"])).unwrap();
+        assert_eq!(vec!["b.txt", "deeper/c.txt"], relative(&root, &files));
+    }
+
+    #[test]
+    fn should_skip_matches_only_the_name() {
+        let patterns = patterns(&["*.tmp", "target"]);
+        assert!(should_skip(Path::new("a/b/c.tmp"), &patterns));
+        assert!(should_skip(Path::new("a/target"), &patterns));
+        assert!(!should_skip(Path::new("target/c.txt"), &patterns));
+        assert!(!should_skip(Path::new("a/c.tmp.txt"), &patterns));
+        assert!(!should_skip(Path::new("a/c.tmp"), &[]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loop_is_not_followed() {
+        let dir = tree();
+        // sub/deeper/loop -> sub
+        std::os::unix::fs::symlink(dir.path().join("sub"), dir.path().join("sub/deeper/loop"))
+            .unwrap();
+        // sub/link.txt -> a.txt
+        std::os::unix::fs::symlink(dir.path().join("a.txt"), dir.path().join("sub/link.txt"))
+            .unwrap();
+        let files = collect_files(dir.path(), true).unwrap();
+        assert_eq!(
+            vec!["a.txt", "sub/b.txt", "sub/deeper/c.txt", "sub/link.txt"],
+            relative(dir.path(), &files)
+        );
+    }
+}
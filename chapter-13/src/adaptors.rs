@@ -0,0 +1,402 @@
+//! More of the adaptors and consumers that `Iterator` provides. The chapter only shows `map`,
+//! `filter`, `sum` and `collect`, but there are many more and they are worth getting to know.
+
+use std::num::ParseIntError;
+
+/// Collapses runs of the same character into one, e.g. `"aaabbc"` becomes `"abc"`.
+///
+/// `peekable` wraps an iterator so that we can look at the next item without taking it. That's
+/// what parsers need all the time: "is the next character part of this token or the start of the
+/// next one?" Here we peek to see if the next character repeats the current one and, if so, skip
+/// it.
+pub fn collapse_runs(input: &str) -> String {
+    let mut chars = input.chars().peekable();
+    let mut collapsed = String::with_capacity(input.len());
+
+    while let Some(c) = chars.next() {
+        collapsed.push(c);
+        // Skip the rest of the run. `chars.next_if_eq(&c)` is a shortcut for this.
+        while chars.peek() == Some(&c) {
+            chars.next();
+        }
+    }
+
+    collapsed
+}
+
+/// The cumulative sums of `nums`, e.g. `[1, 2, 3]` gives `[1, 3, 6]`.
+///
+/// `scan` is like `map` with a bit of state that lives from one item to the next. The closure gets a
+/// `&mut` to the state and returns an `Option`; returning `None` would end the iteration early.
+pub fn running_totals(nums: &[i32]) -> Vec<i32> {
+    nums.iter()
+        .scan(0, |total, &n| {
+            *total += n;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Multiplies all of the numbers together. The product of no numbers is 1, just like the sum of no
+/// numbers is 0.
+///
+/// `fold` is the most general consumer: start with a value and combine it with each item in turn.
+/// `sum` and `product` are special cases of it (`Iterator::product` would do the same as this).
+/// Unlike `scan`, `fold` only gives back the final value.
+// Clippy would rather we used `product()`, but then there'd be no `fold` to look at.
+#[allow(clippy::unnecessary_fold)]
+pub fn product(nums: &[i32]) -> i32 {
+    nums.iter().fold(1, |acc, &n| acc * n)
+}
+
+/// Parses every string, or returns the first error.
+///
+/// `map` gives us an iterator of `Result<i32, ParseIntError>`. We could collect that into a
+/// `Vec<Result<..>>`, but that's awkward to use. `Result` implements `FromIterator` in a special
+/// way: collecting an iterator of `Result<T, E>` into a `Result<Vec<T>, E>` gives `Ok` with all of
+/// the values if every item was `Ok`, or the first `Err`. It stops at that first `Err`, so the
+/// strings after it are never parsed. `Option` works the same way.
+pub fn parse_all(strs: &[&str]) -> Result<Vec<i32>, ParseIntError> {
+    strs.iter().map(|s| s.parse::<i32>()).collect()
+}
+
+/// All of the words in `text`, in order, across every line.
+///
+/// `map` turns each line into an iterator of words, so we'd end up with an iterator of iterators.
+/// `flat_map` maps and then flattens: each inner iterator is drained in turn, one after the other.
+pub fn words_in_lines(text: &str) -> Vec<&str> {
+    text.lines()
+        .flat_map(|line| line.split_whitespace())
+        .collect()
+}
+
+/// Joins the inner vectors into one, e.g. `[[1, 2], [], [3]]` becomes `[1, 2, 3]`.
+///
+/// `flatten` is `flat_map` without the map; it works on any iterator whose items can themselves be
+/// iterated. It also works on an iterator of `Option`s, where it skips the `None`s.
+pub fn flatten_nested(v: Vec<Vec<i32>>) -> Vec<i32> {
+    v.into_iter().flatten().collect()
+}
+
+/// Pairs up the items of `a` and `b` by position, e.g. `[1, 2]` and `['x', 'y']` gives
+/// `[(1, 'x'), (2, 'y')]`.
+///
+/// `zip` stops as soon as either iterator runs out, so if one slice is longer than the other its
+/// extra items are left out. Nothing tells you that happened; check the lengths first if it
+/// matters.
+pub fn pair_up<A: Clone, B: Clone>(a: &[A], b: &[B]) -> Vec<(A, B)> {
+    a.iter().cloned().zip(b.iter().cloned()).collect()
+}
+
+/// The opposite of `pair_up`: splits a list of pairs into a list of firsts and a list of seconds.
+///
+/// `unzip` is a consumer, like `collect`, except that it fills two collections at once.
+pub fn split_pairs<A, B>(pairs: Vec<(A, B)>) -> (Vec<A>, Vec<B>) {
+    pairs.into_iter().unzip()
+}
+
+/// The digits at the start of `s`, e.g. `"123abc"` gives `"123"`. If `s` doesn't start with a
+/// digit this is `""`.
+///
+/// `take_while` yields items until the closure returns `false` for one, then stops for good, even
+/// if later items would pass. That's exactly "the leading run". We want a slice of `s`, not a new
+/// `String`, so we use `char_indices` to find the byte offset where the run ends. Only ASCII
+/// digits count, since those are the ones `parse` understands.
+pub fn leading_digits(s: &str) -> &str {
+    let end = s
+        .char_indices()
+        .take_while(|(_, c)| c.is_ascii_digit())
+        .last()
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    &s[..end]
+}
+
+/// Whatever comes after the leading digits of `s`, e.g. `"123abc"` gives `"abc"`.
+///
+/// `skip_while` is the other half of `take_while`: it drops items while the closure returns `true`
+/// and then yields everything after that. The first item it yields tells us where the rest starts.
+/// If it yields nothing, `s` was all digits (or empty).
+// Clippy would rather we used `find` with the opposite test, but this is about `skip_while`.
+#[allow(clippy::skip_while_next)]
+pub fn after_digits(s: &str) -> &str {
+    s.char_indices()
+        .skip_while(|(_, c)| c.is_ascii_digit())
+        .next()
+        .map(|(i, _)| &s[i..])
+        .unwrap_or("")
+}
+
+/// Splits `items` into groups of neighbours that have the same key, e.g. grouping
+/// `[1, 3, 2, 4, 5]` by `n % 2` gives `[(1, [1, 3]), (0, [2, 4]), (1, [5])]`.
+///
+/// Only neighbours end up together, so a key can have more than one group. That's what we want
+/// for things like runs in a log, and it's cheap: we only compare each key with the one before it.
+/// If you want one group per key, sort first or collect into a `HashMap<K, Vec<T>>` instead.
+///
+/// std doesn't have this for iterators (`slice::chunk_by` is the closest). `fold` would work, but
+/// a plain loop is easier to read when we have to look back at the last group.
+pub fn group_consecutive<T, K: PartialEq, F: FnMut(&T) -> K>(
+    items: Vec<T>,
+    mut key: F,
+) -> Vec<(K, Vec<T>)> {
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    for item in items {
+        let k = key(&item);
+        match groups.last_mut() {
+            Some((last_key, group)) if *last_key == k => group.push(item),
+            _ => groups.push((k, vec![item])),
+        }
+    }
+    groups
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::IntErrorKind;
+
+    #[test]
+    fn collapse_runs_at_the_start() {
+        assert_eq!("abc", collapse_runs("aaabc"));
+    }
+
+    #[test]
+    fn collapse_runs_in_the_middle() {
+        assert_eq!("abc", collapse_runs("abbbc"));
+    }
+
+    #[test]
+    fn collapse_runs_at_the_end() {
+        assert_eq!("abc", collapse_runs("abccc"));
+    }
+
+    #[test]
+    fn collapse_runs_everywhere() {
+        assert_eq!("abc", collapse_runs("aaabbc"));
+    }
+
+    #[test]
+    fn collapse_runs_no_runs() {
+        assert_eq!("abcba", collapse_runs("abcba"));
+    }
+
+    #[test]
+    fn collapse_runs_empty() {
+        assert_eq!("", collapse_runs(""));
+    }
+
+    #[test]
+    fn collapse_runs_unicode() {
+        assert_eq!("é ñ", collapse_runs("éé   ññ"));
+    }
+
+    #[test]
+    fn running_totals_of_some_numbers() {
+        assert_eq!(vec![1, 3, 6], running_totals(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn running_totals_with_negatives() {
+        assert_eq!(vec![5, 2, 2], running_totals(&[5, -3, 0]));
+    }
+
+    #[test]
+    fn running_totals_empty() {
+        assert!(running_totals(&[]).is_empty());
+    }
+
+    #[test]
+    fn product_of_some_numbers() {
+        assert_eq!(24, product(&[2, 3, 4]));
+    }
+
+    #[test]
+    fn product_empty() {
+        assert_eq!(1, product(&[]));
+    }
+
+    #[test]
+    fn product_matches_std() {
+        let nums = [3, -1, 7];
+        assert_eq!(nums.iter().product::<i32>(), product(&nums));
+    }
+
+    #[test]
+    fn parse_all_valid() {
+        assert_eq!(Ok(vec![1, -2, 30]), parse_all(&["1", "-2", "30"]));
+    }
+
+    #[test]
+    fn parse_all_one_invalid() {
+        assert!(parse_all(&["1", "two", "3"]).is_err());
+    }
+
+    #[test]
+    fn parse_all_returns_the_first_error() {
+        // An empty string and a non-digit give different errors, so we can tell which one we got.
+        let err = parse_all(&["1", "", "x"]).unwrap_err();
+        assert_eq!(&IntErrorKind::Empty, err.kind());
+    }
+
+    #[test]
+    fn parse_all_empty() {
+        assert_eq!(Ok(vec![]), parse_all(&[]));
+    }
+
+    #[test]
+    fn words_in_lines_multi_line() {
+        let text = "the quick\n  brown   fox\n\njumps";
+        assert_eq!(
+            vec!["the", "quick", "brown", "fox", "jumps"],
+            words_in_lines(text)
+        );
+    }
+
+    #[test]
+    fn words_in_lines_empty() {
+        assert!(words_in_lines("").is_empty());
+        assert!(words_in_lines("\n \n").is_empty());
+    }
+
+    #[test]
+    fn flatten_nested_vecs() {
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            flatten_nested(vec![vec![1, 2], vec![3], vec![4]])
+        );
+    }
+
+    #[test]
+    fn flatten_nested_with_empty_inner_vecs() {
+        assert_eq!(
+            vec![1, 2],
+            flatten_nested(vec![vec![], vec![1], vec![], vec![2], vec![]])
+        );
+        assert!(flatten_nested(vec![vec![], vec![]]).is_empty());
+        assert!(flatten_nested(vec![]).is_empty());
+    }
+
+    #[test]
+    fn pair_up_equal_lengths() {
+        assert_eq!(
+            vec![(1, 'a'), (2, 'b'), (3, 'c')],
+            pair_up(&[1, 2, 3], &['a', 'b', 'c'])
+        );
+    }
+
+    #[test]
+    fn pair_up_stops_at_the_shorter() {
+        assert_eq!(
+            vec![(1, 'a'), (2, 'b')],
+            pair_up(&[1, 2, 3, 4], &['a', 'b'])
+        );
+        assert_eq!(vec![(1, 'a')], pair_up(&[1], &['a', 'b', 'c']));
+        assert!(pair_up::<i32, char>(&[], &['a']).is_empty());
+    }
+
+    #[test]
+    fn split_pairs_of_some_pairs() {
+        let (names, ages) = split_pairs(vec![("Ann", 31), ("Bo", 4)]);
+        assert_eq!(vec!["Ann", "Bo"], names);
+        assert_eq!(vec![31, 4], ages);
+    }
+
+    #[test]
+    fn zip_then_unzip_round_trips() {
+        let a = vec![String::from("x"), String::from("y")];
+        let b = vec![1.5, 2.5];
+        let (a2, b2) = split_pairs(pair_up(&a, &b));
+        assert_eq!(a, a2);
+        assert_eq!(b, b2);
+    }
+
+    #[test]
+    fn digits_then_letters() {
+        assert_eq!("123", leading_digits("123abc"));
+        assert_eq!("abc", after_digits("123abc"));
+    }
+
+    #[test]
+    fn no_leading_digits() {
+        assert_eq!("", leading_digits("abc"));
+        assert_eq!("abc", after_digits("abc"));
+    }
+
+    #[test]
+    fn all_digits() {
+        assert_eq!("456", leading_digits("456"));
+        assert_eq!("", after_digits("456"));
+    }
+
+    #[test]
+    fn only_the_leading_run() {
+        assert_eq!("1", leading_digits("1a2"));
+        assert_eq!("a2", after_digits("1a2"));
+    }
+
+    #[test]
+    fn digits_then_multibyte_chars() {
+        assert_eq!("42", leading_digits("42€"));
+        assert_eq!("€", after_digits("42€"));
+        assert_eq!("", leading_digits(""));
+        assert_eq!("", after_digits(""));
+    }
+
+    #[test]
+    fn group_words_by_first_letter() {
+        let words = vec![
+            "apple",
+            "avocado",
+            "banana",
+            "blueberry",
+            "cherry",
+            "apricot",
+        ];
+        let groups = group_consecutive(words, |w| w.chars().next());
+        assert_eq!(
+            vec![
+                (Some('a'), vec!["apple", "avocado"]),
+                (Some('b'), vec!["banana", "blueberry"]),
+                (Some('c'), vec!["cherry"]),
+                (Some('a'), vec!["apricot"]),
+            ],
+            groups
+        );
+    }
+
+    #[test]
+    fn group_numbers_by_parity() {
+        let groups = group_consecutive(vec![1, 3, 2, 4, 6, 5], |n| n % 2 == 0);
+        assert_eq!(
+            vec![(false, vec![1, 3]), (true, vec![2, 4, 6]), (false, vec![5])],
+            groups
+        );
+    }
+
+    #[test]
+    fn group_consecutive_keeps_every_item_in_order() {
+        let items: Vec<i32> = (0..20).collect();
+        let groups = group_consecutive(items.clone(), |n| n / 3);
+        assert_eq!(7, groups.len());
+        let flattened: Vec<i32> = groups.into_iter().flat_map(|(_, group)| group).collect();
+        assert_eq!(items, flattened);
+    }
+
+    #[test]
+    fn group_consecutive_one_group() {
+        assert_eq!(
+            vec![((), vec![1, 2, 3])],
+            group_consecutive(vec![1, 2, 3], |_| ())
+        );
+    }
+
+    #[test]
+    fn group_consecutive_empty() {
+        assert!(group_consecutive(Vec::<i32>::new(), |n| *n).is_empty());
+    }
+}
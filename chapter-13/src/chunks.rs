@@ -0,0 +1,113 @@
+/// Groups the items of another iterator into `Vec`s of `size` items each. The last `Vec` has
+/// whatever is left over, so it can be shorter, but it is never empty.
+///
+/// `slice::chunks` does this for slices, where every chunk can be a slice of the original. A
+/// plain iterator has nothing to borrow from, so each chunk has to be collected into a `Vec` of
+/// its own.
+///
+/// This is how adaptors like `map` and `filter` work: a struct that owns the iterator it wraps and
+/// calls its `next` as needed. Nothing happens until someone asks for a chunk.
+#[derive(Debug, Clone)]
+pub struct Chunks<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Chunks<I> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0, like `slice::chunks` does. A chunk with no items in it would never
+    /// use up the iterator, so we'd return empty chunks forever.
+    pub fn new(iter: I, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be greater than zero");
+        Self { iter, size }
+    }
+}
+
+impl<I: Iterator> Iterator for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        // `by_ref` lets `take` borrow the iterator instead of taking ownership of it.
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    /// Every `size` items of the inner iterator (or part of that, at the end) make one chunk.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.div_ceil(self.size),
+            upper.map(|upper| upper.div_ceil(self.size)),
+        )
+    }
+}
+
+/// An extension trait adds methods to types that we didn't write. Because of the blanket `impl`
+/// below, every iterator gets a `chunked` method once this trait is in scope with
+/// `use crate::chunks::IterChunksExt`. Crates like `itertools` are built this way.
+///
+/// The `Sized` bound is needed because `chunked` takes `self` by value.
+pub trait IterChunksExt: Iterator + Sized {
+    /// See `Chunks`.
+    fn chunked(self, size: usize) -> Chunks<Self> {
+        Chunks::new(self, size)
+    }
+}
+
+impl<I: Iterator> IterChunksExt for I {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_chunk_is_shorter() {
+        let chunks: Vec<Vec<i32>> = (1..=7).chunked(3).collect();
+        assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]], chunks);
+    }
+
+    #[test]
+    fn chunks_divide_evenly() {
+        let chunks: Vec<Vec<i32>> = (1..=6).chunked(2).collect();
+        assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5, 6]], chunks);
+    }
+
+    #[test]
+    fn empty_iterator_has_no_chunks() {
+        let mut chunks = std::iter::empty::<i32>().chunked(3);
+        assert_eq!(None, chunks.next());
+    }
+
+    #[test]
+    fn same_as_slice_chunks() {
+        let v: Vec<u8> = (0..20).collect();
+        for size in 1..=21 {
+            let expected: Vec<Vec<u8>> = v.chunks(size).map(|c| c.to_vec()).collect();
+            let actual: Vec<Vec<u8>> = v.iter().copied().chunked(size).collect();
+            assert_eq!(expected, actual, "size {}", size);
+        }
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut chunks = (1..=7).chunked(3);
+        assert_eq!((3, Some(3)), chunks.size_hint());
+        chunks.next();
+        assert_eq!((2, Some(2)), chunks.size_hint());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be greater than zero")]
+    fn zero_size_panics() {
+        let _ = (1..=7).chunked(0);
+    }
+}
@@ -0,0 +1,97 @@
+/// Counts down from `n` to 1, e.g. `Countdown::new(3)` yields 3, 2, 1.
+///
+/// Because we always know exactly how many items are left, `size_hint` can return the same lower
+/// and upper bound. Adaptors and consumers use the hint to plan ahead, e.g. `collect::<Vec<_>>()`
+/// can allocate room for the lower bound before it starts pushing, so an exact hint saves it from
+/// growing the `Vec` as it goes.
+///
+/// `ExactSizeIterator` is a promise that the hint is exact. In return we get its `len` method,
+/// which is computed from `size_hint`.
+#[derive(Debug, Clone)]
+pub struct Countdown {
+    remaining: u32,
+}
+
+impl Countdown {
+    pub fn new(n: u32) -> Self {
+        Self { remaining: n }
+    }
+}
+
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let current = self.remaining;
+        self.remaining -= 1;
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Nothing to implement, `len` has a default implementation that uses `size_hint`.
+impl ExactSizeIterator for Countdown {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_down() {
+        let items: Vec<u32> = Countdown::new(3).collect();
+        assert_eq!(vec![3, 2, 1], items);
+    }
+
+    #[test]
+    fn zero_is_empty() {
+        let mut countdown = Countdown::new(0);
+        assert_eq!(0, countdown.len());
+        assert_eq!(None, countdown.next());
+    }
+
+    #[test]
+    fn len_decreases() {
+        let mut countdown = Countdown::new(3);
+        assert_eq!(3, countdown.len());
+        countdown.next();
+        assert_eq!(2, countdown.len());
+        countdown.next();
+        assert_eq!(1, countdown.len());
+        countdown.next();
+        assert_eq!(0, countdown.len());
+        countdown.next();
+        assert_eq!(0, countdown.len());
+    }
+
+    #[test]
+    fn size_hint_is_exact_throughout() {
+        let mut countdown = Countdown::new(5);
+        for k in (0..=5).rev() {
+            assert_eq!((k, Some(k)), countdown.size_hint());
+            countdown.next();
+        }
+    }
+
+    /// How `collect` uses the hint is up to the standard library, so we only check that the hint
+    /// is exact and that there is room for every item, not the exact capacity.
+    #[test]
+    fn collect_uses_the_hint() {
+        let countdown = Countdown::new(100);
+        assert_eq!((100, Some(100)), countdown.size_hint());
+        assert_eq!(100, countdown.len());
+        let items: Vec<u32> = countdown.collect();
+        assert_eq!((1..=100).rev().collect::<Vec<_>>(), items);
+        assert!(items.capacity() >= 100);
+    }
+}
@@ -0,0 +1,110 @@
+/// Skips items that are equal to the one right before them, e.g. `1, 1, 2, 1` gives `1, 2, 1`.
+/// Only neighbours are compared, so the same value can still come up again later.
+///
+/// `Vec::dedup` does this in place, but it needs the whole `Vec` first. This is lazy: it only
+/// remembers the last item it returned, which is why the items have to be `Clone` (one copy goes
+/// to the caller and one stays here to compare with). `collapse_runs` in `adaptors.rs` does the
+/// same thing for `char`s with `peekable`.
+#[derive(Debug, Clone)]
+pub struct Dedup<I: Iterator> {
+    iter: I,
+    last: Option<I::Item>,
+}
+
+impl<I: Iterator> Dedup<I> {
+    pub fn new(iter: I) -> Self {
+        Self { iter, last: None }
+    }
+}
+
+impl<I> Iterator for Dedup<I>
+where
+    I: Iterator,
+    I::Item: PartialEq + Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        loop {
+            let item = self.iter.next()?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                return Some(item);
+            }
+        }
+    }
+
+    /// Nothing can be a repeat of the first item, so until we've returned something, any items at
+    /// all mean at least one. After that, every item that's left might be a repeat.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let lower = if self.last.is_none() { lower.min(1) } else { 0 };
+        (lower, upper)
+    }
+}
+
+/// Adds `dedup` to every iterator. See `IterChunksExt` in `chunks.rs` for how this works.
+pub trait IterDedupExt: Iterator + Sized {
+    /// See `Dedup`.
+    fn dedup(self) -> Dedup<Self> {
+        Dedup::new(self)
+    }
+}
+
+impl<I: Iterator> IterDedupExt for I {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_consecutive_repeats() {
+        let items: Vec<i32> = [1, 1, 2, 3, 3, 3, 1].into_iter().dedup().collect();
+        assert_eq!(vec![1, 2, 3, 1], items);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(None, std::iter::empty::<i32>().dedup().next());
+    }
+
+    #[test]
+    fn all_equal() {
+        let items: Vec<&str> = ["a"; 5].into_iter().dedup().collect();
+        assert_eq!(vec!["a"], items);
+    }
+
+    #[test]
+    fn no_repeats() {
+        let items: Vec<i32> = (1..=4).dedup().collect();
+        assert_eq!(vec![1, 2, 3, 4], items);
+    }
+
+    #[test]
+    fn same_as_vec_dedup() {
+        let v = vec![5, 5, 4, 4, 4, 5, 0, 0, 9];
+        let mut expected = v.clone();
+        expected.dedup();
+        let actual: Vec<i32> = v.into_iter().dedup().collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn works_with_strings() {
+        let words = ["to", "be", "be", "or", "not", "not", "to", "be"].map(String::from);
+        let items: Vec<String> = words.into_iter().dedup().collect();
+        assert_eq!(vec!["to", "be", "or", "not", "to", "be"], items);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut dedup = [1, 1, 2].into_iter().dedup();
+        assert_eq!((1, Some(3)), dedup.size_hint());
+        dedup.next();
+        assert_eq!((0, Some(2)), dedup.size_hint());
+    }
+}
@@ -0,0 +1,109 @@
+/// Iterates over a slice by keeping two indexes into it: `front` is the next item `next` will
+/// return and `back` is one past the next item `next_back` will return. When they meet, everything
+/// has been returned exactly once, no matter which end each item came from.
+///
+/// Implementing `DoubleEndedIterator` (i.e. `next_back`) is what makes `.rev()` work, along with
+/// other adaptors that need the end of the iterator like `rfind` and `rposition`.
+#[derive(Debug, Clone)]
+pub struct IndexRange<'a, T> {
+    items: &'a [T],
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> IndexRange<'a, T> {
+    pub fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            front: 0,
+            back: items.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IndexRange<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        let item = &self.items[self.front];
+        self.front += 1;
+        Some(item)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IndexRange<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(&self.items[self.back])
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward() {
+        let v = vec![1, 2, 3, 4];
+        let items: Vec<&i32> = IndexRange::new(&v).collect();
+        assert_eq!(vec![&1, &2, &3, &4], items);
+    }
+
+    #[test]
+    fn reversed() {
+        let v = vec![1, 2, 3, 4];
+        let items: Vec<&i32> = IndexRange::new(&v).rev().collect();
+        assert_eq!(vec![&4, &3, &2, &1], items);
+    }
+
+    #[test]
+    fn alternating_meets_in_the_middle() {
+        let v = vec![1, 2, 3, 4, 5];
+        let mut range = IndexRange::new(&v);
+        assert_eq!(Some(&1), range.next());
+        assert_eq!(Some(&5), range.next_back());
+        assert_eq!(Some(&2), range.next());
+        assert_eq!(Some(&4), range.next_back());
+        assert_eq!(Some(&3), range.next());
+        assert_eq!(None, range.next_back());
+        assert_eq!(None, range.next());
+    }
+
+    #[test]
+    fn alternating_yields_each_item_once() {
+        let v: Vec<usize> = (0..10).collect();
+        let mut range = IndexRange::new(&v);
+        let mut seen = Vec::new();
+        loop {
+            let item = if seen.len() % 2 == 0 {
+                range.next()
+            } else {
+                range.next_back()
+            };
+            match item {
+                Some(item) => seen.push(*item),
+                None => break,
+            }
+        }
+        seen.sort();
+        assert_eq!(v, seen);
+    }
+
+    #[test]
+    fn empty() {
+        let v: Vec<i32> = Vec::new();
+        let mut range = IndexRange::new(&v);
+        assert_eq!(None, range.next_back());
+        assert_eq!(None, range.next());
+    }
+}
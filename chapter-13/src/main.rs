@@ -18,9 +18,29 @@ cargo run --package rustlings-chapter-13 -- watch
  */
 #![allow(dead_code, unused_variables, unused_mut)]
 
+use crate::chunks::IterChunksExt;
+use crate::countdown::Countdown;
+use crate::dedup::IterDedupExt;
+use crate::index_range::IndexRange;
+use crate::memoize::Memoize;
+use crate::stack::Stack;
+use crate::windows::IterWindowsExt;
+use crate::words::Words;
 use std::thread;
 use std::time::Duration;
 
+mod adaptors;
+mod chunks;
+mod countdown;
+mod dedup;
+mod index_range;
+mod memoize;
+mod performance;
+mod stack;
+mod util;
+mod windows;
+mod words;
+
 fn main() {
     println!("Chapter 13!");
     closures();
@@ -67,6 +87,7 @@ fn closures() {
     closure_immutable_borrow();
     closure_mutable_borrow();
     closure_move_ownership();
+    memoization();
 }
 
 fn sep(s: &str) {
@@ -143,6 +164,8 @@ fn closure_move_ownership() {
 /// These closures can be called more than once without mutating their environment, which is
 /// important in cases such as calling a closure multiple times concurrently.
 ///
+/// See `util::retry` for an `FnMut` that is called repeatedly until it returns `Ok`.
+///
 /// Here is an example of `FnOnce` which is used similarly to this for `Option::unwrap_or_default`.
 fn unwrap_or_default<T, F>(opt: Option<T>, f: F) -> T
 where
@@ -154,6 +177,22 @@ where
     }
 }
 
+/// # Memoization
+///
+/// A struct can hold a closure in a field. See `memoize.rs` for `Memoize`, which holds a closure and
+/// a `HashMap` of the results it has already returned, so that each key is only calculated once.
+fn memoization() {
+    sep("Memoization");
+    let mut slow_double = Memoize::new(|n: &u32| {
+        println!("calculating slowly...");
+        thread::sleep(Duration::from_millis(32));
+        n * 2
+    });
+    println!("{}", slow_double.get(21));
+    // This one comes from the cache, so there's no "calculating slowly...".
+    println!("{}", slow_double.get(21));
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 /// # Iterators
 fn iterators() {
@@ -161,6 +200,10 @@ fn iterators() {
     the_sum_method_consumes_the_iterator();
     collect_consumes_the_iterator_and_creates_a_vector();
     closures_used_in_iterators_can_also_capture_the_environment();
+    into_iterator_for_a_custom_collection();
+    implementing_iterator();
+    more_adaptors();
+    writing_adaptors();
 }
 
 /// # Iterator Trait
@@ -234,6 +277,99 @@ fn closures_used_in_iterators_can_also_capture_the_environment() {
     println!("Shoes in size 10:\n{:?}", shoes_in_size_10)
 }
 
+/// # IntoIterator
+///
+/// So far we have only iterated over `std` collections. See `stack.rs` for a collection of our own
+/// that can be used in a `for` loop both by value and by reference.
+fn into_iterator_for_a_custom_collection() {
+    let mut stack = Stack::new();
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+
+    for item in &stack {
+        println!("Borrowed from the stack: {}", item);
+    }
+
+    for item in stack {
+        println!("Popped from the stack: {}", item);
+    }
+}
+
+/// # Implementing Iterator
+///
+/// Implementing `Iterator` only takes a `next` function. See `words.rs` for an iterator that
+/// borrows a string and hands out its words one at a time.
+fn implementing_iterator() {
+    for word in Words::new("  safe,  fast, productive.  ") {
+        println!("Word: {}", word);
+    }
+
+    // `IndexRange` in `index_range.rs` also implements `DoubleEndedIterator`, so it can be reversed.
+    let v = vec![1, 2, 3];
+    for n in IndexRange::new(&v).rev() {
+        println!("Backwards: {}", n);
+    }
+
+    // `Countdown` in `countdown.rs` always knows how many items it has left.
+    let countdown = Countdown::new(3);
+    println!("Counting down {} times", countdown.len());
+    for n in countdown {
+        println!("{}...", n);
+    }
+}
+
+/// # More Adaptors
+///
+/// See `adaptors.rs` for adaptors and consumers that the book doesn't get to.
+fn more_adaptors() {
+    println!("Collapsed: {}", adaptors::collapse_runs("Mississippi"));
+    println!(
+        "Running totals: {:?}",
+        adaptors::running_totals(&[1, 2, 3, 4])
+    );
+    println!("Product: {}", adaptors::product(&[1, 2, 3, 4]));
+    println!("Parsed: {:?}", adaptors::parse_all(&["1", "2", "3"]));
+    println!("Parsed: {:?}", adaptors::parse_all(&["1", "two", "3"]));
+    println!(
+        "Words: {:?}",
+        adaptors::words_in_lines("one two\nthree\n\nfour")
+    );
+    println!(
+        "Flattened: {:?}",
+        adaptors::flatten_nested(vec![vec![1, 2], vec![], vec![3]])
+    );
+    let pairs = adaptors::pair_up(&["a", "b", "c"], &[1, 2]);
+    println!("Pairs: {:?}", pairs);
+    println!("Unzipped: {:?}", adaptors::split_pairs(pairs));
+    let size = "250ml";
+    println!(
+        "Number: {}, unit: {}",
+        adaptors::leading_digits(size),
+        adaptors::after_digits(size)
+    );
+    let temperatures = vec![-2, -1, 3, 5, 4, -3];
+    for (below_zero, run) in adaptors::group_consecutive(temperatures, |t| *t < 0) {
+        println!("Below zero: {}, run: {:?}", below_zero, run);
+    }
+}
+
+/// # Writing Adaptors
+///
+/// An adaptor is just an iterator that wraps another iterator. An extension trait with a blanket
+/// `impl` makes it available as a method on every iterator, like the ones in std. See `chunks.rs`,
+/// `windows.rs` and `dedup.rs`.
+fn writing_adaptors() {
+    for chunk in (1..=7).chunked(3) {
+        println!("Chunk: {:?}", chunk);
+    }
+    for window in "rust".chars().windows_iter(2) {
+        println!("Window: {:?}", window);
+    }
+    let readings: Vec<i32> = [20, 20, 21, 21, 21, 20].into_iter().dedup().collect();
+    println!("Changes: {:?}", readings);
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 /// # Improving Our I/O project
 ///
@@ -246,6 +382,11 @@ fn improving_our_io_project() {
 /// # Comparing Performance: Loops vs. Iterators
 ///
 /// See: https://doc.rust-lang.org/book/ch13-04-performance.html
+///
+/// See `performance.rs` for the loop and iterator versions of minigrep's `search` side by side,
+/// with a test that times them on a large input.
 fn comparing_performance() {
-    // Look at the book for this one
+    let contents = "Rust:\nsafe, fast, productive.\nPick three.";
+    println!("loop: {:?}", performance::search_loop("fast", contents));
+    println!("iter: {:?}", performance::search_iter("fast", contents));
 }
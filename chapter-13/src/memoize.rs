@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Wraps a closure and remembers what it returned for each key, so that the closure only runs the
+/// first time a key is asked for. This is useful when the closure is slow (like
+/// `expensive_closure` in `closures()`) and the same keys come up again and again.
+///
+/// - `K` is stored as a `HashMap` key, so it must be `Eq + Hash`. `get` doesn't need to copy the
+///   key: it looks up `&key`, and on a miss it moves `key` into the map. The `Clone` bound isn't
+///   needed by this implementation, and is only there so that `K` is bounded like `V`.
+/// - `V` must be `Clone` because the map keeps the original and the caller gets a copy.
+/// - `F` is `FnMut` so that the closure can keep some state of its own, such as a call counter.
+///
+/// The closure is given `&K` rather than `K` because it only needs to look at the key.
+pub struct Memoize<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: FnMut(&K) -> V,
+{
+    calculation: F,
+    values: HashMap<K, V>,
+}
+
+impl<K, V, F> Memoize<K, V, F>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    F: FnMut(&K) -> V,
+{
+    pub fn new(calculation: F) -> Self {
+        Self {
+            calculation,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Returns the cached value for `key`, calling the closure first if we haven't seen `key`.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.values.get(&key) {
+            return value.clone();
+        }
+        let value = (self.calculation)(&key);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    /// The number of keys that have been calculated so far.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn each_key_is_calculated_once() {
+        let calls = Cell::new(0usize);
+        let mut squares = Memoize::new(|n: &u64| {
+            calls.set(calls.get() + 1);
+            n * n
+        });
+        assert_eq!(4, squares.get(2));
+        assert_eq!(9, squares.get(3));
+        assert_eq!(4, squares.get(2));
+        assert_eq!(9, squares.get(3));
+        assert_eq!(4, squares.get(2));
+        assert_eq!(2, calls.get());
+        assert_eq!(2, squares.len());
+    }
+
+    #[test]
+    fn closure_can_keep_its_own_state() {
+        // An `FnMut` closure that counts its own calls instead of using a `Cell`.
+        let mut calls = 0;
+        let mut lengths = Memoize::new(|s: &String| {
+            calls += 1;
+            s.len()
+        });
+        for word in ["one", "two", "three", "one", "three"] {
+            lengths.get(word.to_string());
+        }
+        assert_eq!(3, lengths.len());
+        drop(lengths);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn starts_empty() {
+        let memo = Memoize::new(|n: &i32| n + 1);
+        assert!(memo.is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+//! Two versions of minigrep's `search` from chapter 12 so that we can compare a `for` loop to an
+//! iterator adaptor chain. The book claims the iterator version is just as fast, because iterators
+//! are a *zero-cost abstraction*: they compile down to about the same code as the loop.
+//!
+//! To see the timings, run the tests in release mode and show their output:
+//!
+//! ```shell
+//! cargo test --release -p chapter-13 performance -- --nocapture
+//! ```
+
+/// The `search` function from chapter 12, using a `for` loop and a mutable `Vec`. This is a copy
+/// of `chapter_12::search`, and the tests check that the two give the same results.
+pub fn search_loop<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let mut results = Vec::new();
+
+    for line in contents.lines() {
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+
+    results
+}
+
+/// The same search written with iterator adaptors, as in §13.3. No mutable state is needed.
+pub fn search_iter<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// Runs `f` a number of times and returns the last result along with the total time taken.
+    fn bench<'a, F>(iterations: usize, f: F) -> (Vec<&'a str>, Duration)
+    where
+        F: Fn() -> Vec<&'a str>,
+    {
+        let start = Instant::now();
+        let mut result = f();
+        for _ in 1..iterations {
+            result = f();
+        }
+        (result, start.elapsed())
+    }
+
+    /// Something big enough to take a measurable amount of time. Every seventh line matches.
+    fn large_input() -> String {
+        (0..100_000)
+            .map(|i| {
+                if i % 7 == 0 {
+                    format!("line {} has the needle in it", i)
+                } else {
+                    format!("line {} is just hay", i)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn search_loop_finds_lines() {
+        let contents = "safe, fast, productive.\nPick three.\nDuct tape.";
+        assert_eq!(
+            vec!["safe, fast, productive."],
+            search_loop("duct", contents)
+        );
+    }
+
+    #[test]
+    fn search_iter_finds_lines() {
+        let contents = "safe, fast, productive.\nPick three.\nDuct tape.";
+        assert_eq!(
+            vec!["safe, fast, productive."],
+            search_iter("duct", contents)
+        );
+    }
+
+    /// Timing varies from machine to machine and run to run, so we only print the times. The
+    /// assertion is that both versions produce exactly the same output as chapter 12's `search`.
+    #[test]
+    fn loop_and_iter_agree_on_large_input() {
+        let contents = large_input();
+        let (from_loop, loop_time) = bench(10, || search_loop("needle", &contents));
+        let (from_iter, iter_time) = bench(10, || search_iter("needle", &contents));
+        println!("search_loop: {:?}", loop_time);
+        println!("search_iter: {:?}", iter_time);
+        assert_eq!(from_loop.len(), 100_000 / 7 + 1);
+        assert_eq!(from_loop, from_iter);
+        assert_eq!(chapter_12::search("needle", &contents), from_loop);
+    }
+}
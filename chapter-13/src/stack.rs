@@ -0,0 +1,117 @@
+/// A last-in-first-out collection so that we have a custom type to iterate over.
+///
+/// A `for` loop works on anything that implements `IntoIterator`. The standard collections
+/// implement it three times: for `Vec<T>` (yields `T`), for `&Vec<T>` (yields `&T`) and for
+/// `&mut Vec<T>` (yields `&mut T`). That's why `for x in v` consumes `v` and `for x in &v` does
+/// not. Here we do the same for `Stack<T>`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// It is conventional to provide `iter()` in addition to `impl IntoIterator for &Stack<T>`.
+    pub fn iter(&self) -> std::iter::Rev<std::slice::Iter<'_, T>> {
+        self.items.iter().rev()
+    }
+}
+
+/// The iterator returned when a `Stack` is consumed by `into_iter()`. It owns the stack and pops
+/// an item each time `next` is called, so the items come out in LIFO order.
+pub struct IntoIter<T>(Stack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
+
+/// `for x in stack` moves the stack into the loop.
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+/// `for x in &stack` borrows the stack. We can't pop from a borrowed stack, so instead we reuse
+/// the slice iterator from the `Vec` and reverse it to get LIFO order.
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Rev<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack() -> Stack<String> {
+        let mut stack = Stack::new();
+        stack.push("first".to_string());
+        stack.push("second".to_string());
+        stack.push("third".to_string());
+        stack
+    }
+
+    #[test]
+    fn for_loop_by_value() {
+        let mut items = Vec::new();
+        for item in stack() {
+            items.push(item);
+        }
+        assert_eq!(vec!["third", "second", "first"], items);
+    }
+
+    #[test]
+    fn for_loop_by_reference() {
+        let stack = stack();
+        let mut items = Vec::new();
+        for item in &stack {
+            items.push(item.as_str());
+        }
+        assert_eq!(vec!["third", "second", "first"], items);
+        // The stack is still usable because we only borrowed it.
+        assert_eq!(3, stack.len());
+    }
+
+    #[test]
+    fn pop_is_lifo() {
+        let mut stack = stack();
+        assert_eq!(Some("third".to_string()), stack.pop());
+        assert_eq!(Some("second".to_string()), stack.pop());
+        assert_eq!(Some("first".to_string()), stack.pop());
+        assert_eq!(None, stack.pop());
+    }
+}
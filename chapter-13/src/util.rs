@@ -0,0 +1,96 @@
+use std::thread;
+use std::time::Duration;
+
+/// Calls `f` up to `attempts` times and returns the first `Ok`, or the last `Err` if every attempt
+/// fails. `f` is always called at least once, even if `attempts` is zero, because otherwise we
+/// would have no `Err` to return.
+///
+/// `f` is `FnMut` because a closure that tries something more than once usually needs to change
+/// some state between attempts (a counter, a connection, etc.). An `Fn` would be too strict for the
+/// caller and an `FnOnce` could only be called once.
+pub fn retry<T, E, F: FnMut() -> Result<T, E>>(attempts: usize, f: F) -> Result<T, E> {
+    retry_with_backoff(attempts, Duration::ZERO, f)
+}
+
+/// Like `retry`, but sleeps between attempts. The sleep starts at `delay` and doubles after each
+/// failure, which gives a struggling service some room to recover.
+pub fn retry_with_backoff<T, E, F>(attempts: usize, delay: Duration, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    let mut delay = delay;
+    let mut result = f();
+    for _ in 1..attempts {
+        if result.is_ok() {
+            break;
+        }
+        if !delay.is_zero() {
+            thread::sleep(delay);
+            delay *= 2;
+        }
+        result = f();
+    }
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn succeeds_on_third_attempt() {
+        let calls = Cell::new(0usize);
+        let result = retry(5, || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(format!("attempt {} failed", calls.get()))
+            } else {
+                Ok(calls.get())
+            }
+        });
+        assert_eq!(Ok(3), result);
+        assert_eq!(3, calls.get());
+    }
+
+    #[test]
+    fn always_fails_returns_last_error() {
+        let calls = Cell::new(0usize);
+        let result: Result<(), String> = retry(4, || {
+            calls.set(calls.get() + 1);
+            Err(format!("attempt {} failed", calls.get()))
+        });
+        assert_eq!(Err("attempt 4 failed".to_string()), result);
+        assert_eq!(4, calls.get());
+    }
+
+    #[test]
+    fn zero_attempts_calls_once() {
+        let calls = Cell::new(0usize);
+        let result: Result<(), ()> = retry(0, || {
+            calls.set(calls.get() + 1);
+            Err(())
+        });
+        assert!(result.is_err());
+        assert_eq!(1, calls.get());
+    }
+
+    #[test]
+    fn backoff_eventually_succeeds() {
+        let calls = Cell::new(0usize);
+        let result = retry_with_backoff(3, Duration::from_millis(1), || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 2 {
+                Err(())
+            } else {
+                Ok("done")
+            }
+        });
+        assert_eq!(Ok("done"), result);
+        assert_eq!(2, calls.get());
+    }
+}
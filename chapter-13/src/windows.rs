@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+
+/// Yields every run of `size` consecutive items from another iterator, e.g. `1, 2, 3, 4` with a
+/// size of 2 gives `[1, 2]`, `[2, 3]`, `[3, 4]`. If there are fewer than `size` items there are no
+/// windows at all.
+///
+/// `slice::windows` does this for slices, and its windows are slices of the original. We only
+/// have an iterator, so we keep the last `size` items in a `VecDeque`. Each step pushes the new
+/// item onto the back and pops the oldest one off the front, both of which are cheap.
+///
+/// Why a `Vec` for each window, and not a slice of the buffer? A slice would borrow from the
+/// `Windows` itself, and `next` would change the buffer while that borrow was still alive.
+/// `Iterator` has no way to say "this item borrows from the iterator" (that's called a lending
+/// iterator), so each window is a copy, and the items have to be `Clone`.
+#[derive(Debug, Clone)]
+pub struct Windows<I: Iterator> {
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+}
+
+impl<I: Iterator> Windows<I> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0, like `slice::windows` does.
+    pub fn new(iter: I, size: usize) -> Self {
+        assert!(size != 0, "window size must be greater than zero");
+        Self {
+            iter,
+            size,
+            window: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        // The first time through this fills the whole window. After that the window is full, so we
+        // drop the oldest item and only need one more.
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+/// Adds `windows_iter` to every iterator. See `IterChunksExt` in `chunks.rs` for how this works.
+/// (It can't be called `windows`, that would be confusing next to `slice::windows`.)
+pub trait IterWindowsExt: Iterator + Sized {
+    /// See `Windows`.
+    fn windows_iter(self, size: usize) -> Windows<Self> {
+        Windows::new(self, size)
+    }
+}
+
+impl<I: Iterator> IterWindowsExt for I {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_same_as_slice_windows(v: &[i32], size: usize) {
+        let expected: Vec<Vec<i32>> = v.windows(size).map(|w| w.to_vec()).collect();
+        let actual: Vec<Vec<i32>> = v.iter().copied().windows_iter(size).collect();
+        assert_eq!(expected, actual, "size {}", size);
+    }
+
+    #[test]
+    fn windows_of_two() {
+        let windows: Vec<Vec<i32>> = (1..=4).windows_iter(2).collect();
+        assert_eq!(vec![vec![1, 2], vec![2, 3], vec![3, 4]], windows);
+    }
+
+    #[test]
+    fn same_as_slice_windows() {
+        let v: Vec<i32> = (0..10).collect();
+        for size in 1..=10 {
+            assert_same_as_slice_windows(&v, size);
+        }
+    }
+
+    #[test]
+    fn size_of_one() {
+        assert_same_as_slice_windows(&[7, 8, 9], 1);
+        let windows: Vec<Vec<i32>> = (7..=9).windows_iter(1).collect();
+        assert_eq!(vec![vec![7], vec![8], vec![9]], windows);
+    }
+
+    #[test]
+    fn size_larger_than_the_input() {
+        assert_same_as_slice_windows(&[1, 2, 3], 4);
+        assert_eq!(None, (1..=3).windows_iter(4).next());
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_same_as_slice_windows(&[], 2);
+        assert_eq!(None, std::iter::empty::<i32>().windows_iter(1).next());
+    }
+
+    #[test]
+    fn stays_done() {
+        let mut windows = (1..=2).windows_iter(2);
+        assert_eq!(Some(vec![1, 2]), windows.next());
+        assert_eq!(None, windows.next());
+        assert_eq!(None, windows.next());
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be greater than zero")]
+    fn zero_size_panics() {
+        let _ = (1..=3).windows_iter(0);
+    }
+}
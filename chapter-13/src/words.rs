@@ -0,0 +1,108 @@
+/// An iterator over the whitespace-separated words in a string. This is what
+/// `str::split_whitespace` does, written out by hand.
+///
+/// It is lazy: nothing is found until `next` is called, and each word is a slice of the original
+/// string, so there is no allocation at all. Compare that to `split_whitespace().collect()`, which
+/// has to allocate a `Vec` big enough to hold every word before you can look at the first one.
+///
+/// The lifetime `'a` ties the words to the string they were found in, so the words can outlive the
+/// `Words` iterator but not the string.
+#[derive(Debug, Clone)]
+pub struct Words<'a> {
+    remaining: &'a str,
+}
+
+impl<'a> Words<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self { remaining: s }
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let start = self.remaining.trim_start();
+        if start.is_empty() {
+            self.remaining = start;
+            return None;
+        }
+        let end = start.find(char::is_whitespace).unwrap_or(start.len());
+        let (word, rest) = start.split_at(end);
+        self.remaining = rest;
+        Some(word)
+    }
+
+    /// `size_hint` lets adaptors like `collect` allocate the right amount up front. It returns a
+    /// lower and an (optional) upper bound on the number of items left. We don't know how many
+    /// words there are without looking, so the lower bound is 0. Each word needs at least one byte
+    /// plus a byte of whitespace between it and the next, so there can't be more than half the
+    /// remaining bytes, rounded up.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining.len().div_ceil(2)))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_same_as_split_whitespace(s: &str) {
+        let expected: Vec<&str> = s.split_whitespace().collect();
+        let actual: Vec<&str> = Words::new(s).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn single_spaces() {
+        assert_same_as_split_whitespace("safe fast productive");
+    }
+
+    #[test]
+    fn leading_and_trailing_spaces() {
+        assert_same_as_split_whitespace("  Pick three.  ");
+    }
+
+    #[test]
+    fn multiple_spaces_tabs_and_newlines() {
+        assert_same_as_split_whitespace("Rust:\n\tsafe,   fast,\r\nproductive.");
+    }
+
+    #[test]
+    fn empty_string() {
+        assert_same_as_split_whitespace("");
+    }
+
+    #[test]
+    fn only_whitespace() {
+        assert_same_as_split_whitespace(" \t\n ");
+    }
+
+    #[test]
+    fn words_outlive_the_iterator() {
+        let s = String::from("Duct tape.");
+        let first = Words::new(&s).next();
+        assert_eq!(Some("Duct"), first);
+    }
+
+    #[test]
+    fn size_hint_upper_bound() {
+        let mut words = Words::new("a b c");
+        assert_eq!((0, Some(3)), words.size_hint());
+        words.next();
+        assert_eq!((0, Some(2)), words.size_hint());
+        assert_eq!(2, words.count());
+    }
+
+    #[test]
+    fn size_hint_when_done() {
+        let mut words = Words::new("a  ");
+        words.next();
+        words.next();
+        assert_eq!((0, Some(0)), words.size_hint());
+    }
+}
@@ -18,31 +18,54 @@ cargo run --package rustlings-chapter-15 -- watch
  */
 #![allow(dead_code, unused_variables, unused_mut)]
 
-// TODO - for next time: demonstrate weak pointers
 // TODO - for next time:get the rustlings exercises ready
 
+use crate::scope_guard::{defer, ScopeGuard};
 use crate::simple_ptr::SimplePtr;
+use crate::simple_rc::{SimpleRc, SimpleWeak};
+use crate::sorted_vec::SortedVec;
+use crate::timer::Timer;
+use crate::typed_id::Id;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+mod scope_guard;
 mod simple_ptr;
+mod simple_rc;
+mod sorted_vec;
+mod timer;
+mod typed_id;
 
 fn main() {
     println!("Chapter 15!");
     sep();
     simple_ptr();
     sep();
+    deref_coercion();
+    sep();
+    newtype_deref();
+    sep();
+    typed_ids();
+    sep();
     std_lib_smart_pointers();
     sep();
+    simple_rc();
+    sep();
     box_t();
     sep();
     dynamic_dispatch_with_box();
     sep();
     std_mem_drop();
     sep();
+    raii_timer();
+    sep();
+    scope_guards();
+    sep();
     ref_cell_example();
     sep();
     memory_leak();
+    sep();
+    weak_pointers();
 }
 
 /// # Smart Pointers
@@ -86,6 +109,76 @@ fn simple_ptr() {
     print_person(&simple_ptr);
     change_person(&mut simple_ptr);
     print_person(&simple_ptr);
+
+    // `SimplePtr` implements `Debug` by passing it on to the `Person`, so it prints the same.
+    println!("{:?}", simple_ptr);
+
+    // The number of items in a slice can be decided at runtime. The `SimplePtr<[u64]>` remembers
+    // how many there are, so it derefs to a `&[u64]` that we can index and iterate over.
+    let ids: Vec<u64> = (1..=4).map(|n| n * 1000).collect();
+    let ids = SimplePtr::from_slice(&ids);
+    println!("{} ids, the first is {}", ids.len(), ids[0]);
+    for id in ids.iter() {
+        println!("id: {}", id);
+    }
+}
+
+/// # Deref Coercion
+///
+/// https://doc.rust-lang.org/book/ch15-02-deref.html#implicit-deref-coercions-with-functions-and-methods
+///
+/// `hello` takes a `&str`, but we can pass it a `&SimplePtr<String>`. The compiler sees that the
+/// types don't match and calls `deref` as many times as it needs to:
+/// `&SimplePtr<String>` -> `&String` (our `Deref`) -> `&str` (`String`'s `Deref`).
+/// This happens at compile time, so there is no runtime cost.
+fn hello(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+fn deref_coercion() {
+    let name = SimplePtr::new(String::from("Rust"));
+    println!("{}", hello(&name));
+
+    // Without deref coercion we would have to write this:
+    println!("{}", hello(&(*name)[..]));
+}
+
+/// # Newtype and Deref
+///
+/// `Deref` isn't only for pointers. Wrapping a type in a newtype hides all of its methods, and
+/// `Deref` is a way to give some of them back. See `sorted_vec.rs` for a `Vec` that stays sorted
+/// but can still be read like a slice.
+///
+/// Be careful with this: the Rust API guidelines say `Deref` should only be implemented by smart
+/// pointers, because the methods that show up through `Deref` can be surprising. For a read-only
+/// view of a collection, like this one, it is common in practice.
+fn newtype_deref() {
+    let mut sorted = SortedVec::new();
+    sorted.insert(3);
+    sorted.insert(1);
+    sorted.insert(2);
+    println!("sorted: {:?}, len: {}", &sorted[..], sorted.len());
+    println!("position of 2: {:?}", sorted.binary_search(&2));
+}
+
+/// # PhantomData
+///
+/// `SimplePtr` isn't the only place that `PhantomData` shows up. See `typed_id.rs` for an ID type
+/// that can't be mixed up with IDs for other types.
+fn typed_ids() {
+    struct User;
+    struct Product;
+
+    fn print_product(id: Id<Product>) {
+        println!("Product ID: {}", id);
+    }
+
+    let user: Id<User> = Id::new(1);
+    let product: Id<Product> = Id::new(1);
+    print_product(product);
+    // Does not compile:
+    // print_product(user);
+    println!("User ID: {:?}", user);
 }
 
 /// Here are examples of some of the smart pointers in the standard library.
@@ -126,6 +219,25 @@ fn std_lib_smart_pointers() {
     println!("{}", ref_cell.borrow());
 }
 
+/// # Reference Counting
+///
+/// https://doc.rust-lang.org/book/ch15-04-rc.html
+///
+/// See `simple_rc.rs` for a simple `Rc`. The count lives on the heap next to the value, so every
+/// handle sees the same count.
+fn simple_rc() {
+    let a = SimpleRc::new(String::from("shared"));
+    println!("count after creating a = {}", a.strong_count());
+    let b = a.clone();
+    println!("count after cloning a into b = {}", a.strong_count());
+    {
+        let c = b.clone();
+        println!("count after cloning b into c = {}", a.strong_count());
+        println!("a, b and c all point to '{}'", *c);
+    }
+    println!("count after c goes out of scope = {}", a.strong_count());
+}
+
 /// # `Box<T>`
 ///
 /// A `Box` is nothing but a strongly-owned pointer to something that is allocated in `new` and
@@ -211,6 +323,34 @@ fn std_mem_drop() {
     println!("Function is till alive but simple_ptr is dropped");
 }
 
+/// # RAII for Things That Aren't Memory
+///
+/// See `timer.rs`. The `Timer` reports how long it was alive when it goes out of scope, which is
+/// at the end of the block here.
+fn raii_timer() {
+    {
+        let _timer = Timer::new("block");
+        let total: u64 = (0..1_000_000).sum();
+        println!("Summed to {}", total);
+    }
+
+    // Careful! `let _ = ...` drops the value immediately, so this timer reports right away.
+    let _ = Timer::new("dropped immediately");
+    println!("raii_timer is done");
+}
+
+/// # Running Any Code on Drop
+///
+/// See `scope_guard.rs`. This is how you get something like Go's `defer` in Rust.
+fn scope_guards() {
+    defer!(println!("scope_guards: deferred, so this prints last"));
+
+    let guard = ScopeGuard::new(|| println!("scope_guards: this never prints"));
+    guard.dismiss();
+
+    println!("scope_guards: this prints first");
+}
+
 /// # Interior Mutability: A Mutable Borrow to an Immutable Value
 ///
 /// This is a type that lets you work around the borrowing rules with runtime checks.
@@ -290,9 +430,70 @@ fn memory_leak() {
     // println!("a next item = {:?}", a.tail());
 }
 
+/// # Preventing Reference Cycles with Weak Pointers
+///
+/// https://doc.rust-lang.org/book/ch15-06-reference-cycles.html#preventing-reference-cycles-turning-an-rct-into-a-weakt
+///
+/// See `SimpleWeak` in `simple_rc.rs`. A parent owns its children with strong pointers, and the
+/// children point back at the parent with weak pointers, so there is no cycle of strong pointers.
+fn weak_pointers() {
+    struct TreeNode {
+        name: &'static str,
+        parent: RefCell<Option<SimpleWeak<TreeNode>>>,
+        children: RefCell<Vec<SimpleRc<TreeNode>>>,
+    }
+
+    impl Drop for TreeNode {
+        fn drop(&mut self) {
+            println!("dropping {}", self.name);
+        }
+    }
+
+    let leaf = SimpleRc::new(TreeNode {
+        name: "leaf",
+        parent: RefCell::new(None),
+        children: RefCell::new(Vec::new()),
+    });
+
+    {
+        let branch = SimpleRc::new(TreeNode {
+            name: "branch",
+            parent: RefCell::new(None),
+            children: RefCell::new(vec![leaf.clone()]),
+        });
+        *leaf.parent.borrow_mut() = Some(branch.downgrade());
+
+        if let Some(parent) = leaf.parent.borrow().as_ref().and_then(|p| p.upgrade()) {
+            println!("leaf parent = {}", parent.name);
+        }
+        println!(
+            "branch strong = {}, weak = {}",
+            branch.strong_count(),
+            branch.weak_count()
+        );
+        println!("leaf strong = {}", leaf.strong_count());
+    }
+
+    // `branch` was dropped at the end of the block even though `leaf` still points at it.
+    let parent = leaf.parent.borrow().as_ref().and_then(|p| p.upgrade());
+    println!("leaf parent is gone: {}", parent.is_none());
+    println!("leaf strong = {}", leaf.strong_count());
+}
+
 /// Used to separate printed things.
 fn sep() {
     println!();
     println!("----------------------------------------------------------------------------------");
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deref_coercion_from_simple_ptr_to_str() {
+        let name = SimplePtr::new(String::from("Ferris"));
+        assert_eq!("Hello, Ferris!", hello(&name));
+    }
+}
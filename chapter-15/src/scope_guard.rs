@@ -0,0 +1,89 @@
+/// `Timer` is one specific thing to do on drop. A `ScopeGuard` generalizes the idea: it holds any
+/// closure and runs it when the guard goes out of scope. This is Go's `defer`, and it's what the
+/// `scopeguard` crate provides.
+///
+/// The closure is `FnOnce` (not `FnMut`) because it only ever runs once. Every `FnMut` closure is
+/// also `FnOnce`, so this accepts the most closures.
+pub struct ScopeGuard<F: FnOnce()> {
+    /// This is `None` after the guard has been dismissed. `Option::take` lets us move the closure
+    /// out of `&mut self` in `drop` so that we can call it.
+    action: Option<F>,
+}
+
+impl<F: FnOnce()> ScopeGuard<F> {
+    pub fn new(action: F) -> Self {
+        Self {
+            action: Some(action),
+        }
+    }
+
+    /// Cancels the action. Taking `self` by value means the guard can't be used after this.
+    pub fn dismiss(mut self) {
+        self.action = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        if let Some(action) = self.action.take() {
+            action();
+        }
+    }
+}
+
+/// Runs the code at the end of the enclosing scope. The guard's variable name is hidden by macro
+/// hygiene, so it can't be dismissed. Use `ScopeGuard::new` if you need that.
+///
+/// ```ignore
+/// defer!(println!("this prints last"));
+/// println!("this prints first");
+/// ```
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::scope_guard::ScopeGuard::new(|| { $($body)*; });
+    };
+}
+
+pub(crate) use defer;
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn runs_at_end_of_scope() {
+        let ran = Cell::new(false);
+        {
+            let _guard = ScopeGuard::new(|| ran.set(true));
+            assert!(!ran.get());
+        }
+        assert!(ran.get());
+    }
+
+    #[test]
+    fn does_not_run_after_dismiss() {
+        let ran = Cell::new(false);
+        {
+            let guard = ScopeGuard::new(|| ran.set(true));
+            guard.dismiss();
+        }
+        assert!(!ran.get());
+    }
+
+    #[test]
+    fn defer_runs_in_reverse_order() {
+        let order = std::cell::RefCell::new(Vec::new());
+        {
+            defer!(order.borrow_mut().push(1));
+            defer!(order.borrow_mut().push(2));
+            order.borrow_mut().push(0);
+        }
+        // Locals are dropped in the reverse order that they were declared.
+        assert_eq!(vec![0, 2, 1], *order.borrow());
+    }
+}
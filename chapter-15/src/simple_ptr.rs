@@ -1,46 +1,76 @@
 use std::alloc;
 use std::alloc::Layout;
+use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
 
 /// A simple and probably badly implemented simple pointer. It will allocate space for `T` and
 /// drop that memory when dropped.
-pub struct SimplePtr<T> {
-    /// The raw pointer can be represented by a `*u8`. We don't need to care about the size of the
-    /// thing pointed to by the pointer because we will allocate and deallocate the correct amount
-    /// of space starting at that address. Note: This might not be the correct way to do it! It
-    /// seems to work OK.
-    ptr: *mut u8,
+///
+/// `T: ?Sized` means that `T` doesn't have to have a size that is known at compile time, so that
+/// we can have a `SimplePtr<[T]>` (see `from_slice`). Every generic parameter is `Sized` unless
+/// we say otherwise.
+pub struct SimplePtr<T: ?Sized> {
+    /// When `T` is sized this is just an address. When `T` is a slice, it is a "fat" pointer: the
+    /// address and the length of the slice together, which is how `&[T]` works too. That's what
+    /// lets `get` turn it back into a `&[T]`, and lets `drop` know how many items to drop.
+    ptr: *mut T,
+    /// The size and alignment that we allocated with. `dealloc` needs the exact same one back.
     layout: Layout,
-    /// When we have a generic `T` and nowhere to "put" it, we can use `PhantomData` to hold the
-    /// "typeness" of the object.
+    /// A `*mut T` doesn't tell the compiler that we own a `T`. `PhantomData` does, which matters
+    /// when it checks what dropping a `SimplePtr<T>` might do.
     _t: PhantomData<T>,
 }
 
 // By implementing the `drop` trait, we ensure our memory always gets cleaned up.
 // See https://doc.rust-lang.org/book/ch15-03-drop.html#running-code-on-cleanup-with-the-drop-trait
-impl<T> Drop for SimplePtr<T> {
+impl<T: ?Sized> Drop for SimplePtr<T> {
     fn drop(&mut self) {
         println!(
             "SimplePtr: freeing memory with this layout: {:?}",
             self.layout
         );
-        unsafe { alloc::dealloc(self.ptr, self.layout) }
+        unsafe {
+            // Run `T`'s destructor before freeing the memory, otherwise anything that `T` owns
+            // (like the heap buffer of a `String`) would leak. For a slice this drops every item.
+            std::ptr::drop_in_place(self.ptr);
+            // Nothing was allocated for something with no size, see `allocate`.
+            if self.layout.size() != 0 {
+                alloc::dealloc(self.ptr as *mut u8, self.layout)
+            }
+        }
+    }
+}
+
+/// Allocates memory for `layout`, which will hold one or more `T`s.
+///
+/// Allocating zero bytes is undefined behavior, and we'd need to for a zero-sized type or an
+/// empty slice. There is nothing to store, so we use a made up ("dangling") address that is
+/// correctly aligned for `T` instead, and `drop` knows not to free it.
+fn allocate<T>(layout: Layout) -> *mut T {
+    if layout.size() == 0 {
+        return NonNull::dangling().as_ptr();
+    }
+    println!(
+        "SimplePtr: allocating memory with this layout: {:?}",
+        layout
+    );
+    let ptr = unsafe { alloc::alloc(layout) };
+    if ptr.is_null() {
+        alloc::handle_alloc_error(layout);
     }
+    ptr as *mut T
 }
 
 impl<T> SimplePtr<T> {
     pub fn new(item: T) -> Self {
         let layout = Layout::for_value(&item);
-        let ptr: *mut u8;
+        let ptr: *mut T = allocate(layout);
         unsafe {
-            println!(
-                "SimplePtr: allocating memory with this layout: {:?}",
-                layout
-            );
-            ptr = alloc::alloc(layout);
-            let p = ptr as *mut T;
-            *p = item;
+            // `*ptr = item` would try to drop the "old" value at `ptr`, but the memory is
+            // uninitialized so there is no old value. `write` moves `item` in without dropping.
+            ptr.write(item);
         }
 
         Self {
@@ -49,25 +79,49 @@ impl<T> SimplePtr<T> {
             _t: PhantomData::default(),
         }
     }
+}
 
-    pub fn get_mut(&mut self) -> &mut T {
-        unsafe {
-            let p = self.ptr as *mut T;
-            &mut *p
+impl<T: Clone> SimplePtr<[T]> {
+    /// Copies a slice onto the heap. The length is only known at runtime, so this can't be a
+    /// `SimplePtr<[T; N]>`; it is a `SimplePtr<[T]>`, which is what `Box<[T]>` is too.
+    ///
+    /// `Layout::array` is the size and alignment of `len` `T`s side by side. It returns an error
+    /// if that would be too big to allocate, which we turn into a panic like `Vec` does.
+    ///
+    /// If one of the `clone` calls panics, the items that were already cloned and the memory are
+    /// leaked. That's safe, just wasteful. `Box` and `Vec` are careful to clean up in that case.
+    pub fn from_slice(src: &[T]) -> Self {
+        let layout = Layout::array::<T>(src.len()).expect("the slice is too big to allocate");
+        let data: *mut T = allocate(layout);
+        for (i, item) in src.iter().enumerate() {
+            unsafe {
+                // `add` moves forward by `i` `T`s, not `i` bytes.
+                data.add(i).write(item.clone());
+            }
+        }
+
+        Self {
+            // Put the address and the length together to make a `*mut [T]`.
+            ptr: std::ptr::slice_from_raw_parts_mut(data, src.len()),
+            layout,
+            _t: PhantomData,
         }
     }
+}
+
+impl<T: ?Sized> SimplePtr<T> {
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.ptr }
+    }
 
     pub fn get(&self) -> &T {
-        unsafe {
-            let p = self.ptr as *const T;
-            &*p
-        }
+        unsafe { &*self.ptr }
     }
 }
 
 // We can treat the object like a `&T` with `Deref` and `DerefMut`. See the following section
 // for more: https://doc.rust-lang.org/book/ch15-02-deref.html#treating-smart-pointers-like-regular
-impl<T> Deref for SimplePtr<T> {
+impl<T: ?Sized> Deref for SimplePtr<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -75,8 +129,151 @@ impl<T> Deref for SimplePtr<T> {
     }
 }
 
-impl<T> DerefMut for SimplePtr<T> {
+impl<T: ?Sized> DerefMut for SimplePtr<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.get_mut()
     }
 }
+
+// Formatting a `SimplePtr` formats the value it points to, the same as `Box` does. Passing `f`
+// along (instead of using `write!(f, "{:?}", ...)`) keeps any flags, like `{:#?}` or `{:>8}`.
+//
+// ```ignore
+// println!("{:?}", SimplePtr::new(vec![1, 2, 3])); // [1, 2, 3]
+// println!("{}", SimplePtr::new(42)); // 42
+// ```
+//
+// (These examples can't be doc tests because this is a binary crate, so they are checked by the
+// tests below instead.)
+
+impl<T: ?Sized + Debug> Debug for SimplePtr<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.get(), f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for SimplePtr<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.get(), f)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_is_the_same_as_the_value() {
+        let value = vec![1, 2, 3];
+        let ptr = SimplePtr::new(value.clone());
+        assert_eq!(format!("{:?}", value), format!("{:?}", ptr));
+        assert_eq!(format!("{:#?}", value), format!("{:#?}", ptr));
+    }
+
+    #[test]
+    fn display_is_the_same_as_the_value() {
+        let ptr = SimplePtr::new(42);
+        assert_eq!("42", format!("{}", ptr));
+        assert_eq!(format!("{:>5}", 42), format!("{:>5}", ptr));
+    }
+
+    #[test]
+    fn debug_of_a_struct() {
+        #[derive(Debug)]
+        struct Person {
+            name: String,
+        }
+        let ptr = SimplePtr::new(Person {
+            name: "Ferris".to_string(),
+        });
+        assert_eq!("Person { name: \"Ferris\" }", format!("{:?}", ptr));
+    }
+
+    // The slice tests use `unsafe` code, so they should also pass under Miri, which checks for
+    // out-of-bounds access, leaks and double frees. Run it after changing `from_slice` or `drop`:
+    // `cargo +nightly miri test -p chapter-15 --bin chapter-15`. (Miri can't run the `trybuild`
+    // test in `tests/compile_fail.rs`, which is why it's left out.)
+
+    #[test]
+    fn slice_from_numbers() {
+        let ptr = SimplePtr::from_slice(&[1, 2, 3]);
+        assert_eq!(3, ptr.len());
+        assert_eq!(1, ptr[0]);
+        assert_eq!(3, ptr[2]);
+        assert_eq!(vec![1, 2, 3], ptr.iter().copied().collect::<Vec<_>>());
+        assert_eq!(&[1, 2, 3], &*ptr);
+    }
+
+    #[test]
+    fn slice_can_be_changed() {
+        let mut ptr = SimplePtr::from_slice(&[1, 2, 3]);
+        ptr[1] = 20;
+        for n in ptr.iter_mut() {
+            *n *= 2;
+        }
+        assert_eq!(&[2, 40, 6], &*ptr);
+    }
+
+    #[test]
+    fn slice_of_strings_is_a_copy() {
+        let strings = vec!["a".to_string(), "bc".to_string()];
+        let ptr = SimplePtr::from_slice(&strings);
+        drop(strings);
+        assert_eq!("bc", ptr[1]);
+        assert_eq!(
+            vec!["a", "bc"],
+            ptr.iter().map(|s| s.as_str()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn slice_drops_every_item_once() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let drops = Rc::new(Cell::new(0));
+
+        #[derive(Clone)]
+        struct Counted(Rc<Cell<usize>>);
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let items = vec![
+            Counted(drops.clone()),
+            Counted(drops.clone()),
+            Counted(drops.clone()),
+        ];
+        let ptr = SimplePtr::from_slice(&items);
+        drop(items);
+        assert_eq!(3, drops.get());
+        drop(ptr);
+        assert_eq!(6, drops.get());
+    }
+
+    #[test]
+    fn empty_slice() {
+        let ptr = SimplePtr::<[String]>::from_slice(&[]);
+        assert!(ptr.is_empty());
+        assert_eq!(None, ptr.iter().next());
+    }
+
+    #[test]
+    fn slice_debug() {
+        let ptr = SimplePtr::from_slice(&[1, 2, 3]);
+        assert_eq!("[1, 2, 3]", format!("{:?}", ptr));
+    }
+
+    #[test]
+    fn zero_sized_type() {
+        let ptr = SimplePtr::new(());
+        assert_eq!((), *ptr);
+    }
+}
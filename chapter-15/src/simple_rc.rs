@@ -0,0 +1,370 @@
+use std::alloc;
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+
+/// The part of a `SimpleRc` that lives on the heap. There is only one of these no matter how many
+/// handles there are, and every handle points at it. The counts have to live here, next to the
+/// value, so that every handle sees the same numbers.
+///
+/// The counts are `Cell`s because handles only have a `&RcBox`, but cloning and dropping them has
+/// to change the counts. (This is the interior mutability from `ref_cell_example`.)
+///
+/// There are two counts because there are two things to free at different times:
+/// - The value is dropped when the last `SimpleRc` goes away (`strong` reaches zero).
+/// - The `RcBox` itself is freed when there is nothing left pointing at it at all, so it has to
+///   wait for the `SimpleWeak`s too (`weak` reaches zero).
+///
+/// Like `Rc`, all of the strong handles together hold one weak count between them, so `weak` is
+/// the number of `SimpleWeak`s plus one while there are any `SimpleRc`s. That way there's only
+/// one place that frees the `RcBox`: whoever takes `weak` to zero.
+struct RcBox<T> {
+    strong: Cell<usize>,
+    weak: Cell<usize>,
+    /// `ManuallyDrop` stops the value from being dropped again when we free the `RcBox`. We drop it
+    /// ourselves, earlier, when `strong` reaches zero.
+    value: ManuallyDrop<T>,
+}
+
+/// A simple reference-counted pointer, like `Rc`. `SimplePtr` has exactly one owner. Any number of
+/// `SimpleRc`s can share one value: cloning a `SimpleRc` doesn't clone the value, it adds one to
+/// the count and hands out another pointer to the same place. Dropping a handle takes one off, and
+/// whichever handle takes the count to zero drops the value and frees the memory.
+///
+/// Because the value is shared, we only ever hand out `&T`. There is no `DerefMut`; put a
+/// `RefCell` inside if you need to change the value.
+///
+/// The raw pointer makes `SimpleRc` neither `Send` nor `Sync`, which is what we want. The count is
+/// a plain `usize`, so two threads changing it at once could lose an update. `Arc` uses atomics
+/// for its count so that it can be shared between threads.
+pub struct SimpleRc<T> {
+    ptr: *mut RcBox<T>,
+    /// Tells the compiler that dropping a `SimpleRc<T>` may drop a `T`, even though all it can see
+    /// is a raw pointer.
+    _t: PhantomData<RcBox<T>>,
+}
+
+impl<T> SimpleRc<T> {
+    pub fn new(value: T) -> Self {
+        let layout = Layout::new::<RcBox<T>>();
+        unsafe {
+            let ptr = alloc::alloc(layout) as *mut RcBox<T>;
+            if ptr.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+            ptr.write(RcBox {
+                strong: Cell::new(1),
+                weak: Cell::new(1),
+                value: ManuallyDrop::new(value),
+            });
+            Self {
+                ptr,
+                _t: PhantomData,
+            }
+        }
+    }
+
+    /// The number of `SimpleRc`s pointing at this value, including this one.
+    ///
+    /// `Rc::strong_count` is an associated function (`Rc::strong_count(&rc)`) instead of a method.
+    /// `Rc` derefs to `T`, and if `T` had a `strong_count` method of its own, `rc.strong_count()`
+    /// would be ambiguous to a reader. We keep it simple here and make it a method.
+    pub fn strong_count(&self) -> usize {
+        self.inner().strong.get()
+    }
+
+    /// The number of `SimpleWeak`s pointing at this value. This doesn't include the weak count
+    /// that the strong handles share.
+    pub fn weak_count(&self) -> usize {
+        self.inner().weak.get() - 1
+    }
+
+    /// Makes a `SimpleWeak` that points at the same value, but doesn't keep it alive.
+    pub fn downgrade(&self) -> SimpleWeak<T> {
+        let weak = &self.inner().weak;
+        weak.set(weak.get() + 1);
+        SimpleWeak { ptr: self.ptr }
+    }
+
+    fn inner(&self) -> &RcBox<T> {
+        // The `RcBox` lives at least as long as any handle, and we are one of the handles.
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T> Clone for SimpleRc<T> {
+    fn clone(&self) -> Self {
+        let strong = &self.inner().strong;
+        strong.set(strong.get() + 1);
+        Self {
+            ptr: self.ptr,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for SimpleRc<T> {
+    fn drop(&mut self) {
+        let strong = self.inner().strong.get() - 1;
+        self.inner().strong.set(strong);
+        if strong == 0 {
+            // We were the last strong handle, so drop the value. Any `SimpleWeak`s can still see
+            // the counts, so the `RcBox` stays until we have given up the shared weak count too.
+            unsafe {
+                ManuallyDrop::drop(&mut (*self.ptr).value);
+                release_weak(self.ptr);
+            }
+        }
+    }
+}
+
+impl<T> Deref for SimpleRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+/// A pointer to the value in a `SimpleRc` that doesn't keep the value alive, like `rc::Weak`.
+///
+/// Two `SimpleRc`s that point at each other keep each other's counts above zero forever, so
+/// neither value is ever dropped (see `memory_leak` in `main.rs`). The usual fix is to decide which
+/// direction "owns" the other. A tree owns its children, so a parent holds `SimpleRc`s to its
+/// children, and each child points back at its parent with a `SimpleWeak`.
+///
+/// Because the value might already be gone, a `SimpleWeak` can't give you a `&T`. You have to
+/// `upgrade` it to a `SimpleRc` first, which fails if there are no strong handles left.
+pub struct SimpleWeak<T> {
+    ptr: *mut RcBox<T>,
+}
+
+impl<T> SimpleWeak<T> {
+    /// Returns a new strong handle to the value, or `None` if the value has already been dropped.
+    pub fn upgrade(&self) -> Option<SimpleRc<T>> {
+        let strong = self.strong();
+        if strong.get() == 0 {
+            return None;
+        }
+        strong.set(strong.get() + 1);
+        Some(SimpleRc {
+            ptr: self.ptr,
+            _t: PhantomData,
+        })
+    }
+
+    /// The number of `SimpleRc`s pointing at the value, which is 0 once it has been dropped.
+    pub fn strong_count(&self) -> usize {
+        self.strong().get()
+    }
+
+    // The `RcBox` isn't freed until the weak count reaches zero, and we are part of that count, so
+    // the counts are always there. The value might not be, or it might be in the middle of being
+    // dropped, so we borrow just the count we need and never the whole `RcBox`.
+
+    fn strong(&self) -> &Cell<usize> {
+        unsafe { &(*self.ptr).strong }
+    }
+
+    fn weak(&self) -> &Cell<usize> {
+        unsafe { &(*self.ptr).weak }
+    }
+}
+
+impl<T> Clone for SimpleWeak<T> {
+    fn clone(&self) -> Self {
+        let weak = self.weak();
+        weak.set(weak.get() + 1);
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for SimpleWeak<T> {
+    fn drop(&mut self) {
+        unsafe { release_weak(self.ptr) }
+    }
+}
+
+/// Takes one off the weak count and frees the `RcBox` if that was the last one.
+///
+/// # Safety
+///
+/// `ptr` must point to an `RcBox` that hasn't been freed, and the caller must own one of its weak
+/// counts.
+unsafe fn release_weak<T>(ptr: *mut RcBox<T>) {
+    let weak = &(*ptr).weak;
+    weak.set(weak.get() - 1);
+    if weak.get() == 0 {
+        // The value was dropped when `strong` reached zero, and `ManuallyDrop` means it won't be
+        // dropped again here. All that's left is the memory.
+        alloc::dealloc(ptr as *mut u8, Layout::new::<RcBox<T>>());
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Counts how many times it has been dropped.
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn deref() {
+        let rc = SimpleRc::new(String::from("shared"));
+        assert_eq!("shared", rc.as_str());
+        assert_eq!(6, rc.len());
+    }
+
+    #[test]
+    fn clones_share_the_value() {
+        let a = SimpleRc::new(vec![1, 2, 3]);
+        let b = a.clone();
+        assert!(std::ptr::eq(&*a, &*b));
+        assert_eq!(2, a.strong_count());
+        assert_eq!(2, b.strong_count());
+    }
+
+    #[test]
+    fn strong_count_follows_clones_and_drops() {
+        let a = SimpleRc::new(0);
+        assert_eq!(1, a.strong_count());
+        let b = a.clone();
+        let c = b.clone();
+        assert_eq!(3, a.strong_count());
+        drop(b);
+        assert_eq!(2, a.strong_count());
+        drop(c);
+        assert_eq!(1, a.strong_count());
+    }
+
+    #[test]
+    fn value_drops_once_when_the_last_handle_dies() {
+        let drops = Cell::new(0);
+        let a = SimpleRc::new(DropCounter(&drops));
+        let handles: Vec<_> = (0..5).map(|_| a.clone()).collect();
+        assert_eq!(6, a.strong_count());
+
+        drop(a);
+        assert_eq!(0, drops.get());
+        let mut handles = handles.into_iter();
+        let last = handles.next().unwrap();
+        drop(handles);
+        assert_eq!(0, drops.get());
+        assert_eq!(1, last.strong_count());
+
+        drop(last);
+        assert_eq!(1, drops.get());
+    }
+
+    #[test]
+    fn value_that_owns_heap_memory() {
+        // We can't see a leak from here, but Miri would report the `String` if it wasn't freed.
+        let a = SimpleRc::new(String::from("freed when the last handle is dropped"));
+        let b = a.clone();
+        drop(a);
+        assert_eq!("freed when the last handle is dropped", *b);
+    }
+
+    #[test]
+    fn upgrade_while_alive() {
+        let rc = SimpleRc::new(5);
+        let weak = rc.downgrade();
+        assert_eq!(1, rc.weak_count());
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(5, *upgraded);
+        assert_eq!(2, rc.strong_count());
+    }
+
+    #[test]
+    fn upgrade_after_the_value_is_gone() {
+        let drops = Cell::new(0);
+        let rc = SimpleRc::new(DropCounter(&drops));
+        let weak = rc.downgrade();
+        let weak2 = weak.clone();
+        assert_eq!(2, rc.weak_count());
+
+        drop(rc);
+        assert_eq!(1, drops.get());
+        assert_eq!(0, weak.strong_count());
+        assert!(weak.upgrade().is_none());
+        assert!(weak2.upgrade().is_none());
+    }
+
+    #[test]
+    fn weak_does_not_keep_the_value_alive() {
+        let drops = Cell::new(0);
+        let rc = SimpleRc::new(DropCounter(&drops));
+        let weaks: Vec<_> = (0..3).map(|_| rc.downgrade()).collect();
+        drop(rc);
+        assert_eq!(1, drops.get());
+        drop(weaks);
+        assert_eq!(1, drops.get());
+    }
+
+    /// A tree node that owns its children and can see its parent.
+    struct Node<'a> {
+        parent: RefCell<Option<SimpleWeak<Node<'a>>>>,
+        children: RefCell<Vec<SimpleRc<Node<'a>>>>,
+        _drops: DropCounter<'a>,
+    }
+
+    impl<'a> Node<'a> {
+        fn new(drops: &'a Cell<usize>) -> SimpleRc<Self> {
+            SimpleRc::new(Node {
+                parent: RefCell::new(None),
+                children: RefCell::new(Vec::new()),
+                _drops: DropCounter(drops),
+            })
+        }
+    }
+
+    #[test]
+    fn parent_child_cycle_with_a_weak_back_reference() {
+        let drops = Cell::new(0);
+        let parent = Node::new(&drops);
+        let child = Node::new(&drops);
+        parent.children.borrow_mut().push(child.clone());
+        *child.parent.borrow_mut() = Some(parent.downgrade());
+
+        // The child can get to its parent, and the parent is only counted once.
+        let upgraded = child.parent.borrow().as_ref().unwrap().upgrade().unwrap();
+        assert!(std::ptr::eq(&*parent, &*upgraded));
+        drop(upgraded);
+        assert_eq!(1, parent.strong_count());
+        assert_eq!(2, child.strong_count());
+
+        // The parent has no other strong handles, so it goes away and takes the child's handle
+        // with it. Had the back reference been a `SimpleRc`, neither node would ever be dropped.
+        drop(parent);
+        assert_eq!(1, drops.get());
+        assert_eq!(1, child.strong_count());
+        assert!(child.parent.borrow().as_ref().unwrap().upgrade().is_none());
+
+        drop(child);
+        assert_eq!(2, drops.get());
+    }
+
+    #[test]
+    fn value_holding_a_weak_to_itself() {
+        // Dropping the value drops the only `SimpleWeak`, while the strong handle that is doing
+        // the dropping still has its shared weak count. This is why that shared count exists.
+        let drops = Cell::new(0);
+        let node = Node::new(&drops);
+        *node.parent.borrow_mut() = Some(node.downgrade());
+        drop(node);
+        assert_eq!(1, drops.get());
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::Deref;
+
+/// A `Vec` that is always sorted. This is the *newtype* pattern: a tuple struct with one field that
+/// wraps an existing type so that we can control how it is used.
+///
+/// The only way to add an item is `insert`, which puts it in the right place. Everything that only
+/// reads the items (`len`, `iter`, `first`, `binary_search`, `contains`, indexing...) comes from
+/// `Deref<Target = [T]>`, so we don't have to write all of those wrappers by hand.
+///
+/// We deliberately do *not* implement `DerefMut`. If we did, `sorted.push(x)` wouldn't compile
+/// (slices can't grow) but `sorted[0] = x` or `sorted.reverse()` would, and the vec would no longer
+/// be sorted.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SortedVec<T: Ord>(Vec<T>);
+
+impl<T: Ord> SortedVec<T> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// `binary_search` returns `Ok(index)` if an equal item is already there, or `Err(index)` for
+    /// where it would go. Either way that's a good place to insert.
+    pub fn insert(&mut self, item: T) {
+        let index = match self.0.binary_search(&item) {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+        self.0.insert(index, item);
+    }
+
+    /// Gives back the inner `Vec`. It's fine to let the caller change it now, because it's no
+    /// longer a `SortedVec`.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+/// Sort once up front, then the invariant holds.
+impl<T: Ord> From<Vec<T>> for SortedVec<T> {
+    fn from(mut items: Vec<T>) -> Self {
+        items.sort();
+        Self(items)
+    }
+}
+
+impl<T: Ord> Deref for SortedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_out_of_order() {
+        let mut sorted = SortedVec::new();
+        for n in [5, 1, 4, 2, 3] {
+            sorted.insert(n);
+        }
+        assert_eq!(vec![1, 2, 3, 4, 5], sorted.into_inner());
+    }
+
+    #[test]
+    fn insert_duplicates() {
+        let mut sorted = SortedVec::new();
+        for n in [2, 1, 2, 1] {
+            sorted.insert(n);
+        }
+        assert_eq!(vec![1, 1, 2, 2], sorted.into_inner());
+    }
+
+    #[test]
+    fn from_unsorted_vec() {
+        let sorted = SortedVec::from(vec!["c", "a", "b"]);
+        assert_eq!(vec!["a", "b", "c"], sorted.into_inner());
+    }
+
+    #[test]
+    fn binary_search_through_deref() {
+        let sorted = SortedVec::from(vec![30, 10, 20]);
+        assert_eq!(Ok(1), sorted.binary_search(&20));
+        assert_eq!(Err(3), sorted.binary_search(&40));
+    }
+
+    #[test]
+    fn slice_methods_through_deref() {
+        let sorted = SortedVec::from(vec![3, 1, 2]);
+        assert_eq!(3, sorted.len());
+        assert_eq!(Some(&1), sorted.first());
+        assert_eq!(Some(&3), sorted.last());
+        assert_eq!(2, sorted[1]);
+        assert!(sorted.contains(&2));
+        assert_eq!(6, sorted.iter().sum::<i32>());
+    }
+
+    #[test]
+    fn deref_coercion_to_slice() {
+        fn total(items: &[i32]) -> i32 {
+            items.iter().sum()
+        }
+        let sorted = SortedVec::from(vec![1, 2, 3]);
+        assert_eq!(6, total(&sorted));
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// Receives the label and the elapsed time when a `Timer` is dropped.
+type Reporter = Box<dyn FnMut(&str, Duration)>;
+
+/// RAII isn't only about memory. Any resource that needs to be "closed" when we are done with it
+/// can be handled by `Drop`: files, locks, network connections... or, in this case, a stopwatch.
+///
+/// A `Timer` starts timing when it is created and reports the elapsed time when it is dropped, so
+/// timing a block of code is as simple as creating a `Timer` at the top of the block.
+pub struct Timer {
+    label: String,
+    start: Instant,
+    /// By default this prints to stdout. Tests can swap it out to capture the report instead.
+    report: Reporter,
+}
+
+impl Timer {
+    pub fn new(label: &str) -> Self {
+        Self::with_reporter(label, |label, elapsed| {
+            println!("Timer: {} took {:?}", label, elapsed)
+        })
+    }
+
+    /// Calls `report` with the label and elapsed time when the `Timer` is dropped.
+    pub fn with_reporter<F>(label: &str, report: F) -> Self
+    where
+        F: FnMut(&str, Duration) + 'static,
+    {
+        Self {
+            label: label.to_string(),
+            start: Instant::now(),
+            report: Box::new(report),
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        (self.report)(&self.label, self.start.elapsed());
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn reports_elapsed_time_on_drop() {
+        let reported = Rc::new(RefCell::new(None));
+        let reported_clone = Rc::clone(&reported);
+        let timer = Timer::with_reporter("sleepy", move |label, elapsed| {
+            *reported_clone.borrow_mut() = Some((label.to_string(), elapsed));
+        });
+
+        thread::sleep(Duration::from_millis(20));
+        // Nothing has been reported yet because the timer is still alive.
+        assert!(reported.borrow().is_none());
+        drop(timer);
+
+        let (label, elapsed) = reported.borrow_mut().take().unwrap();
+        assert_eq!("sleepy", label);
+        assert!(elapsed >= Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+/// `SimplePtr` uses `PhantomData` to hold the "typeness" of a `T` that it doesn't store directly.
+/// Here is another common use: an ID that is just a number at runtime, but that carries the type
+/// it identifies at compile time. An `Id<User>` and an `Id<Product>` are different types, so the
+/// compiler won't let us pass one where the other is expected.
+///
+/// `PhantomData` has no size, so an `Id<T>` is the same size as a `u64`.
+pub struct Id<T>(u64, PhantomData<T>);
+
+impl<T> Id<T> {
+    pub fn new(value: u64) -> Self {
+        Self(value, PhantomData)
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+// We implement these by hand instead of deriving them. `#[derive(Clone)]` would only implement
+// `Clone` for `Id<T>` when `T: Clone`, but we never store a `T` so that bound would be pointless.
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> Debug for Id<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Id<{}>({})", std::any::type_name::<T>(), self.0)
+    }
+}
+
+impl<T> Display for Id<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+// Passing an `Id<User>` where an `Id<Product>` is expected is tested with `trybuild` in
+// `tests/compile_fail.rs`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User;
+    struct Product;
+
+    fn product_name(id: Id<Product>) -> String {
+        format!("product {}", id)
+    }
+
+    #[test]
+    fn get() {
+        let id: Id<User> = Id::new(42);
+        assert_eq!(42, id.get());
+    }
+
+    #[test]
+    fn display() {
+        let id: Id<Product> = Id::new(7);
+        assert_eq!("7", id.to_string());
+        assert_eq!("product 7", product_name(id));
+    }
+
+    #[test]
+    fn copy_and_eq_without_bounds_on_t() {
+        // `User` is not `Copy` or `PartialEq`, but `Id<User>` is.
+        let a: Id<User> = Id::new(1);
+        let b = a;
+        assert_eq!(a, b);
+        assert_ne!(a, Id::new(2));
+    }
+
+    #[test]
+    fn same_size_as_u64() {
+        assert_eq!(size_of::<u64>(), size_of::<Id<User>>());
+    }
+}
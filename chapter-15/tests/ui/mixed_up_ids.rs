@@ -0,0 +1,18 @@
+// `chapter-15` is a binary so we can't `use` it here, but we can include the module's source.
+#[path = "../../src/typed_id.rs"]
+mod typed_id;
+
+use typed_id::Id;
+
+struct User;
+struct Product;
+
+fn delete_product(id: Id<Product>) {
+    println!("deleting product {}", id);
+}
+
+fn main() {
+    let user: Id<User> = Id::new(1);
+    // DOES NOT COMPILE: an `Id<User>` is not an `Id<Product>` even though both are a `u64` inside.
+    delete_product(user);
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Spawns `threads` threads that each add one to a shared counter `increments` times, and returns
+/// the final count.
+///
+/// An `AtomicUsize` can be changed through a shared reference, like a `Mutex`, but there is no lock
+/// and no guard. `fetch_add` is a single instruction that the CPU promises no other thread can
+/// interrupt. When the shared state is one number (a counter, a flag, an id generator) atomics are
+/// simpler and faster than a mutex. Once you need to update two things together, like the message
+/// and the counter in `mutexes()`, you need a `Mutex`, because there is no atomic for "both".
+///
+/// ## Ordering
+///
+/// Every atomic operation takes an `Ordering`, which says what the *other* memory around it is
+/// guaranteed to look like to other threads.
+///
+/// - `SeqCst` is the strongest: all threads see all `SeqCst` operations in the same order. It is
+///   the safe default and what we use here.
+/// - `Relaxed` only guarantees that the operation itself is atomic. That is actually enough for a
+///   counter that nobody reads until the threads are joined (`join` synchronizes for us), and it
+///   can be faster on some CPUs.
+/// - `Acquire`/`Release` are for when the atomic is guarding other data, e.g. a flag that says
+///   "the buffer is ready".
+///
+/// If you aren't sure, use `SeqCst`.
+pub fn count_with_atomic(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    counter.load(Ordering::SeqCst)
+}
+
+/// The same thing with `Arc<Mutex<usize>>`, for comparison. Each increment takes the lock, adds
+/// one, and releases the lock when the guard is dropped.
+pub fn count_with_mutex(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    *counter.lock().unwrap() += 1;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let count = *counter.lock().unwrap();
+    count
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREADS: usize = 8;
+    const INCREMENTS: usize = 10_000;
+
+    #[test]
+    fn atomic_counts_every_increment() {
+        assert_eq!(THREADS * INCREMENTS, count_with_atomic(THREADS, INCREMENTS));
+    }
+
+    #[test]
+    fn mutex_counts_every_increment() {
+        assert_eq!(THREADS * INCREMENTS, count_with_mutex(THREADS, INCREMENTS));
+    }
+
+    #[test]
+    fn no_threads() {
+        assert_eq!(0, count_with_atomic(0, INCREMENTS));
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// Which half of the work a thread was doing when it logged an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    One,
+    Two,
+}
+
+/// Spawns `threads` threads that each do some "phase one" work, wait at a `Barrier`, and then do
+/// some "phase two" work. Returns a log of `(thread, phase, when)` entries, one for each thread
+/// finishing each phase.
+///
+/// `Barrier::new(n)` blocks every thread that calls `wait` until `n` threads have called it, then
+/// releases them all at once. No thread can start phase two until every thread has finished phase
+/// one, so in the log every `Phase::One` timestamp is before every `Phase::Two` timestamp. Without
+/// the barrier, a fast thread could finish both phases while a slow thread is still in phase one.
+///
+/// A barrier is handy when each phase reads what every thread wrote in the previous phase, e.g.
+/// the steps of a simulation.
+pub fn two_phases(threads: usize) -> Vec<(usize, Phase, Instant)> {
+    let barrier = Arc::new(Barrier::new(threads));
+    let log = Arc::new(Mutex::new(Vec::new()));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|id| {
+            let barrier = Arc::clone(&barrier);
+            let log = Arc::clone(&log);
+            thread::spawn(move || {
+                log.lock().unwrap().push((id, Phase::One, Instant::now()));
+                barrier.wait();
+                log.lock().unwrap().push((id, Phase::Two, Instant::now()));
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let log = log.lock().unwrap().clone();
+    log
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_phase_one_comes_before_every_phase_two() {
+        let log = two_phases(8);
+        assert_eq!(16, log.len());
+        let last_one = log
+            .iter()
+            .filter(|(_, phase, _)| *phase == Phase::One)
+            .map(|(_, _, when)| *when)
+            .max()
+            .unwrap();
+        let first_two = log
+            .iter()
+            .filter(|(_, phase, _)| *phase == Phase::Two)
+            .map(|(_, _, when)| *when)
+            .min()
+            .unwrap();
+        assert!(last_one <= first_two, "log out of order: {:?}", log);
+    }
+
+    #[test]
+    fn every_thread_does_both_phases() {
+        let log = two_phases(4);
+        for id in 0..4 {
+            for phase in [Phase::One, Phase::Two] {
+                let count = log
+                    .iter()
+                    .filter(|(i, p, _)| *i == id && *p == phase)
+                    .count();
+                assert_eq!(1, count, "thread {} {:?}", id, phase);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+/// A queue that holds at most `capacity` items and is shared between threads. The `Mutex` protects
+/// the `VecDeque`, and the `Condvar` lets a thread sleep until another thread tells it that the
+/// queue has changed.
+///
+/// Without a `Condvar`, a consumer waiting for an item would have to lock, look, unlock, sleep, and
+/// try again, like the third thread in `mutexes()`. `Condvar::wait` instead gives up the lock and
+/// sleeps until `notify_one` or `notify_all` is called, then takes the lock back before returning.
+#[derive(Debug)]
+pub struct BoundedQueue {
+    capacity: usize,
+    state: Arc<(Mutex<VecDeque<i32>>, Condvar)>,
+}
+
+impl BoundedQueue {
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a bounded queue needs room for at least one item"
+        );
+        Self {
+            capacity,
+            state: Arc::new((Mutex::new(VecDeque::new()), Condvar::new())),
+        }
+    }
+
+    /// Adds an item, waiting first if the queue is full.
+    pub fn push(&self, item: i32) {
+        let (lock, condvar) = &*self.state;
+        let mut queue = lock.lock().unwrap();
+        // `wait` can return even though nobody called `notify` (a *spurious wakeup*), and another
+        // thread might have got to the queue first. So we check the condition in a `while` loop,
+        // never with an `if`.
+        while queue.len() == self.capacity {
+            queue = condvar.wait(queue).unwrap();
+        }
+        queue.push_back(item);
+        // Producers and consumers both wait on the same `Condvar`, so wake them all and let each
+        // one check whether it can go on.
+        condvar.notify_all();
+    }
+
+    /// Removes the oldest item, waiting first if the queue is empty.
+    pub fn pop(&self) -> i32 {
+        let (lock, condvar) = &*self.state;
+        let mut queue = lock.lock().unwrap();
+        while queue.is_empty() {
+            queue = condvar.wait(queue).unwrap();
+        }
+        let item = queue.pop_front().unwrap();
+        condvar.notify_all();
+        item
+    }
+}
+
+/// Cloning the queue clones the `Arc`, so both clones share the same items.
+impl Clone for BoundedQueue {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+/// Runs one producer thread that pushes `0..count` and one consumer thread that pops `count` items,
+/// and returns what the consumer received.
+pub fn produce_and_consume(count: i32, capacity: usize) -> Vec<i32> {
+    let queue = BoundedQueue::new(capacity);
+
+    let producer_queue = queue.clone();
+    let producer = thread::spawn(move || {
+        for item in 0..count {
+            producer_queue.push(item);
+        }
+    });
+
+    let consumer = thread::spawn(move || (0..count).map(|_| queue.pop()).collect());
+
+    producer.join().unwrap();
+    consumer.join().unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumer_receives_everything_in_order() {
+        let received = produce_and_consume(1000, 4);
+        assert_eq!((0..1000).collect::<Vec<_>>(), received);
+    }
+
+    #[test]
+    fn capacity_of_one() {
+        let received = produce_and_consume(50, 1);
+        assert_eq!((0..50).collect::<Vec<_>>(), received);
+    }
+
+    #[test]
+    fn pop_waits_for_push() {
+        let queue = BoundedQueue::new(2);
+        let consumer_queue = queue.clone();
+        let consumer = thread::spawn(move || consumer_queue.pop());
+        queue.push(7);
+        assert_eq!(7, consumer.join().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one item")]
+    fn zero_capacity_panics() {
+        BoundedQueue::new(0);
+    }
+}
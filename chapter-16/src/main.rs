@@ -19,11 +19,19 @@ cargo run --package rustlings-chapter-16 -- watch
  */
 #![allow(dead_code, unused_variables, unused_mut)]
 
-use std::ops::{Deref, DerefMut};
+use std::ops::DerefMut;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+mod atomic_counter;
+mod barrier;
+mod condvar;
+mod poisoning;
+mod send_sync;
+mod shutdown;
+
 /// Note: this entire chapter says nothing about `async`, `await`. The chapter was probably written
 /// before those keywords were introduced. It is less likely that you will use `std::thread` for
 /// concurrency these days, and more likely that you will use things like `Stream`s from the
@@ -38,6 +46,16 @@ fn main() {
     channels();
     sep();
     mutexes();
+    sep();
+    mutex_poisoning();
+    sep();
+    atomics();
+    sep();
+    barriers();
+    sep();
+    condvars();
+    sep();
+    sync_and_send();
 }
 
 /// With a `thread` we can run code in-parallel to the main thread.
@@ -180,24 +198,59 @@ fn mutexes() {
         }
     });
 
-    let handle_3 = thread::spawn(move || {
-        loop {
-            let mut lock = data.lock().unwrap();
-            let (message, counter) = lock.deref();
-            if *counter == 20 {
-                println!("Counter reached 20, threads are done with their loops!");
-                break;
-            }
-            println!("{}", message);
-            // Release the lock before sleeping!
-            drop(lock);
-            thread::sleep(Duration::from_micros(10));
-        }
-    });
+    // The third thread prints messages until we tell it to stop. See `shutdown.rs`.
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let handle_3 = shutdown::spawn_monitor(data.clone(), shutdown.clone());
 
     handle_1.join().unwrap();
     handle_2.join().unwrap();
+    // Both workers are done, so tell the monitor to stop.
+    shutdown.store(true, Ordering::Relaxed);
     handle_3.join().unwrap();
+    let counter = data.lock().unwrap().1;
+    println!(
+        "Threads are done with their loops, the counter is {}",
+        counter
+    );
+}
+
+/// Every `lock().unwrap()` above would panic if another thread had panicked while holding the lock.
+/// See `poisoning.rs` for what poisoning is and how to get the data back with
+/// `PoisonError::into_inner`.
+fn mutex_poisoning() {
+    println!("Mutex poisoning:");
+    let data = Arc::new(Mutex::new(0u32));
+    poisoning::poison(&data, 42);
+    println!("Poisoned: {}", data.is_poisoned());
+    println!("Recovered: {}", poisoning::recover(&data));
+}
+
+/// A `Mutex` is more than we need when the shared state is a single number. See
+/// `atomic_counter.rs` for a counter built on `AtomicUsize` next to the same counter built on
+/// `Arc<Mutex<usize>>`, and a note on choosing a memory `Ordering`.
+fn atomics() {
+    println!("Atomics:");
+    println!(
+        "Atomic count: {}",
+        atomic_counter::count_with_atomic(4, 1000)
+    );
+    println!("Mutex count: {}", atomic_counter::count_with_mutex(4, 1000));
+}
+
+/// A `Barrier` makes a group of threads wait for each other. See `barrier.rs` for threads that
+/// all finish one phase of work before any of them starts the next.
+fn barriers() {
+    println!("Barriers:");
+    for (thread, phase, _) in barrier::two_phases(3) {
+        println!("Thread {} finished phase {:?}", thread, phase);
+    }
+}
+
+/// A `Condvar` lets a thread sleep until another thread tells it something has changed. See
+/// `condvar.rs` for a bounded producer/consumer queue built from a `Mutex` and a `Condvar`.
+fn condvars() {
+    println!("Condvars:");
+    println!("Consumed: {:?}", condvar::produce_and_consume(10, 3));
 }
 
 /// The `Sync` and `Send` traits are built-in language markers for types. These traits have no
@@ -215,7 +268,13 @@ fn mutexes() {
 ///
 /// This stuff gets confusing. Maybe we will learn about it if we go through the `Rustnomicon`.
 /// For now, know that a type needs to be `Send` in order for you to pass it into a thread.
-fn sync_and_send() {}
+///
+/// See `send_sync.rs` for compile-time checks of which types are `Send` and `Sync`, and
+/// `tests/ui` for the ones that fail to compile (moving an `Rc` into a thread, and a struct that
+/// holds an `Rc`).
+fn sync_and_send() {
+    send_sync::assertions();
+}
 
 /// Used to separate printed things.
 fn sep() {
@@ -0,0 +1,66 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+/// Spawns a thread that takes the lock, writes `value`, and then panics while still holding the
+/// lock. When a `MutexGuard` is dropped during a panic, the `Mutex` is marked as *poisoned*. From
+/// then on every call to `lock()` returns `Err(PoisonError)` instead of `Ok(guard)`.
+///
+/// Poisoning is a warning, not a lock. The data is still there, and the `PoisonError` holds the
+/// guard. It is up to us to decide whether the data can still be trusted.
+pub fn poison(data: &Arc<Mutex<u32>>, value: u32) {
+    let data = Arc::clone(data);
+    let handle = thread::spawn(move || {
+        let mut guard = data.lock().unwrap();
+        *guard = value;
+        panic!("the thread panicked while holding the lock");
+    });
+    // The panic comes back to us as an `Err` from `join`.
+    assert!(handle.join().is_err());
+}
+
+/// Takes the lock from another thread, even if the mutex has been poisoned. `PoisonError::into_inner`
+/// hands back the guard that `lock` would have returned if nothing had gone wrong.
+///
+/// This is the right thing to do when every write leaves the data in a valid state, as it does for
+/// a single `u32`. If a panic could happen halfway through updating several fields, the data might
+/// be inconsistent, and `lock().unwrap()` (giving up) would be the safer choice.
+pub fn recover(data: &Arc<Mutex<u32>>) -> u32 {
+    let data = Arc::clone(data);
+    thread::spawn(move || {
+        let guard = data.lock().unwrap_or_else(PoisonError::into_inner);
+        *guard
+    })
+    .join()
+    .unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_poisons_the_mutex() {
+        let data = Arc::new(Mutex::new(0));
+        assert!(!data.is_poisoned());
+        poison(&data, 7);
+        assert!(data.is_poisoned());
+        assert!(data.lock().is_err());
+    }
+
+    #[test]
+    fn recover_gets_the_last_written_value() {
+        let data = Arc::new(Mutex::new(0));
+        poison(&data, 42);
+        assert_eq!(42, recover(&data));
+    }
+
+    #[test]
+    fn recover_works_without_poisoning() {
+        let data = Arc::new(Mutex::new(5));
+        assert_eq!(5, recover(&data));
+    }
+}
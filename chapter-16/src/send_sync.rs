@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// These functions do nothing at runtime. They exist so that the compiler checks the bound:
+/// `assert_send::<T>()` only compiles if `T` is `Send`. It is a common trick for turning "is this
+/// type `Send`?" into a compile error when the answer is no.
+pub fn assert_send<T: Send>() {}
+
+pub fn assert_sync<T: Sync>() {}
+
+/// All fields are `Send` and `Sync`, so the compiler implements both for us.
+pub struct AllSend {
+    pub name: String,
+    pub count: Arc<u32>,
+}
+
+/// One `Rc` field is enough to make the whole struct `!Send` and `!Sync`. See
+/// `tests/ui/struct_with_rc_is_not_send.rs`.
+pub struct HasRc {
+    pub name: String,
+    pub count: Rc<u32>,
+}
+
+/// These are the assertions that compile. The ones that don't are in `tests/ui`.
+pub fn assertions() {
+    // Plain data is both.
+    assert_send::<i32>();
+    assert_sync::<i32>();
+    assert_send::<String>();
+    assert_sync::<String>();
+    assert_send::<AllSend>();
+    assert_sync::<AllSend>();
+
+    // `Arc` is the thread-safe `Rc`.
+    assert_send::<Arc<i32>>();
+    assert_sync::<Arc<i32>>();
+
+    // `RefCell` can be moved to another thread, but it can't be shared between threads, because
+    // its borrow tracking isn't thread-safe. Wrapping it in a `Mutex` makes it `Sync`.
+    assert_send::<RefCell<i32>>();
+    assert_sync::<Mutex<RefCell<i32>>>();
+
+    // Does not compile: `Rc` is neither `Send` nor `Sync`.
+    // assert_send::<Rc<i32>>();
+    // assert_send::<HasRc>();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn i32_is_send() {
+        assert_send::<i32>();
+    }
+
+    #[test]
+    fn struct_of_send_fields_is_send() {
+        assert_send::<AllSend>();
+        assert_sync::<AllSend>();
+    }
+
+    #[test]
+    fn send_struct_can_move_into_a_thread() {
+        let value = AllSend {
+            name: String::from("counter"),
+            count: Arc::new(3),
+        };
+        let handle = thread::spawn(move || format!("{} {}", value.name, value.count));
+        assert_eq!("counter 3", handle.join().unwrap());
+    }
+}
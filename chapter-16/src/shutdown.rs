@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Spawns the monitor thread from `mutexes()`: it prints the latest message until it is told to
+/// stop.
+///
+/// The original version stopped when the counter reached 20. That only works if the monitor
+/// happens to know how many updates the other threads will make, and if one of them makes one
+/// fewer, the monitor spins forever. Here the thread that owns the work decides when it is done by
+/// setting `shutdown` to `true`. The monitor checks the flag once per loop, so it stops within one
+/// sleep of the flag being set, whatever the counter says.
+///
+/// `Relaxed` is enough for the flag because it doesn't guard any other data; the message is behind
+/// the `Mutex`. See `atomic_counter.rs` for more about `Ordering`.
+pub fn spawn_monitor(data: Arc<Mutex<(String, u32)>>, shutdown: Arc<AtomicBool>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        while !shutdown.load(Ordering::Relaxed) {
+            let lock = data.lock().unwrap();
+            println!("{}", lock.0);
+            // Release the lock before sleeping!
+            drop(lock);
+            thread::sleep(Duration::from_micros(10));
+        }
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn monitor_stops_when_flag_is_set() {
+        let data = Arc::new(Mutex::new((String::from("empty"), 0)));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let monitor = spawn_monitor(Arc::clone(&data), Arc::clone(&shutdown));
+        shutdown.store(true, Ordering::Relaxed);
+
+        // `join` has no timeout, so join on another thread and wait for it with one. If the monitor
+        // ignored the flag, `recv_timeout` would give up instead of the test hanging forever.
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || tx.send(monitor.join()).unwrap());
+        let joined = rx.recv_timeout(Duration::from_secs(5));
+        assert!(matches!(joined, Ok(Ok(()))), "the monitor did not stop");
+    }
+
+    #[test]
+    fn monitor_stops_whatever_the_counter_is() {
+        // The old monitor waited for the counter to reach 20, so this would have spun forever.
+        let data = Arc::new(Mutex::new((String::from("stuck"), 3)));
+        let shutdown = Arc::new(AtomicBool::new(true));
+        spawn_monitor(data, shutdown).join().unwrap();
+    }
+}
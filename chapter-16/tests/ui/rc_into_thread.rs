@@ -0,0 +1,13 @@
+// `Rc` counts its references without atomics, so two threads changing the count at the same time
+// could corrupt it. `Rc` is therefore not `Send`, and `thread::spawn` refuses a closure that owns
+// one.
+use std::rc::Rc;
+use std::thread;
+
+fn main() {
+    let shared = Rc::new(5);
+    let handle = thread::spawn(move || {
+        println!("{}", shared);
+    });
+    handle.join().unwrap();
+}
@@ -0,0 +1,14 @@
+// A struct is only `Send` if all of its fields are. This is a copy of `HasRc` from
+// `src/send_sync.rs`, which is `!Send` because of its `Rc` field.
+use std::rc::Rc;
+
+struct HasRc {
+    name: String,
+    count: Rc<u32>,
+}
+
+fn assert_send<T: Send>() {}
+
+fn main() {
+    assert_send::<HasRc>();
+}
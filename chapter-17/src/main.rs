@@ -9,6 +9,9 @@
 
 use anyhow::{ensure, Result};
 
+mod object_safety;
+mod percentage;
+
 fn main() {
     println!("Chapter 17: Object-Oriented Features!");
     sep();
@@ -80,6 +83,9 @@ pub struct NotEncapsulated {
     pub public_data: i32,
 }
 
+// `Encapsulated` checks its value at runtime in `new` and `set_value`. See `percentage.rs` for a
+// type that uses `TryFrom` to do the same, so that a `Percentage` over 100 can never exist.
+
 /// # Inheritance
 ///
 /// Rust kind of does have inheritance, in a way. You can't inherit a struct's data (i.e. fields),
@@ -220,3 +226,6 @@ fn use_the_above_functions() {
 fn holding_a_vector_of_pets_requires_dynamic_dispatch() {
     let pets: Vec<Box<dyn Pet>> = vec![Box::new(Cat), Box::new(Dog)];
 }
+
+// Not every trait can be used as a `dyn Pet` like this. See `object_safety.rs` for the rules, and
+// `tests/ui/not_object_safe.rs` for a trait that the compiler won't make into a trait object.
@@ -0,0 +1,107 @@
+use std::fmt::Debug;
+
+/// # Object Safety
+///
+/// Not every trait can be used as a trait object. To make a `dyn Trait`, the compiler builds a
+/// vtable: one function pointer for each method. A trait is *object safe* (the newer name is
+/// *dyn compatible*) when that is possible. Roughly, every method must:
+///
+/// - Have no generic type parameters. A generic method is really a different function for every
+///   `T`, and a vtable can't hold infinitely many function pointers.
+/// - Take `self` by reference (`&self`, `&mut self`, `Box<Self>`...). Taking `self` by value needs
+///   to know the size of `Self`, which a `dyn Trait` doesn't.
+/// - Not return `Self`. The caller wouldn't know how big the returned value is. This is why
+///   `Clone` is not object safe.
+///
+/// A method that breaks the rules can be kept out of the vtable with `where Self: Sized`. It then
+/// can't be called on a `dyn Trait`, but the rest of the trait can.
+///
+/// Also, the trait must not require `Self: Sized` itself, e.g. by having `Sized` as a supertrait.
+///
+/// This trait has a generic method, so `Box<dyn NotObjectSafe>` does not compile. See
+/// `tests/ui/not_object_safe.rs`.
+pub trait NotObjectSafe {
+    fn process<T: Debug>(&self, t: T) -> String;
+}
+
+/// The object-safe version of the same idea. Instead of a generic `T`, `process` takes a trait
+/// object, so there is only one `process` function and it fits in a vtable. The price is dynamic
+/// dispatch on the argument as well.
+pub trait ObjectSafe {
+    fn process(&self, t: &dyn Debug) -> String;
+
+    /// Breaks the rules by returning `Self`, but `where Self: Sized` leaves it out of the vtable.
+    fn duplicate(&self) -> Self
+    where
+        Self: Sized;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Printer;
+
+impl NotObjectSafe for Printer {
+    fn process<T: Debug>(&self, t: T) -> String {
+        format!("{:?}", t)
+    }
+}
+
+impl ObjectSafe for Printer {
+    fn process(&self, t: &dyn Debug) -> String {
+        format!("{:?}", t)
+    }
+
+    fn duplicate(&self) -> Self {
+        *self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Shouter;
+
+impl ObjectSafe for Shouter {
+    fn process(&self, t: &dyn Debug) -> String {
+        format!("{:?}!", t).to_uppercase()
+    }
+
+    fn duplicate(&self) -> Self {
+        *self
+    }
+}
+
+/// Works because `ObjectSafe` can be a trait object.
+pub fn process_all(processors: &[Box<dyn ObjectSafe>], t: &dyn Debug) -> Vec<String> {
+    processors.iter().map(|p| p.process(t)).collect()
+}
+
+// Does not compile:
+// pub fn process_all_generic(processors: &[Box<dyn NotObjectSafe>]) {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_object_safe_still_works_with_generics() {
+        assert_eq!("[1, 2]", NotObjectSafe::process(&Printer, vec![1, 2]));
+        assert_eq!("\"hi\"", NotObjectSafe::process(&Printer, "hi"));
+    }
+
+    #[test]
+    fn object_safe_in_a_box() {
+        let processors: Vec<Box<dyn ObjectSafe>> = vec![Box::new(Printer), Box::new(Shouter)];
+        assert_eq!(
+            vec!["\"hi\"".to_string(), "\"HI\"!".to_string()],
+            process_all(&processors, &"hi")
+        );
+    }
+
+    #[test]
+    fn sized_only_method_on_a_concrete_type() {
+        let printer = Printer.duplicate();
+        assert_eq!("7", ObjectSafe::process(&printer, &7));
+    }
+}
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A whole-number percentage from 0 to 100. Like `Encapsulated`, the field is private, so the
+/// only way to get a `Percentage` is through `TryFrom`, which checks the value. Every `Percentage`
+/// that exists is valid, and code that receives one never needs to check it again. This is what
+/// people mean by "make invalid states unrepresentable".
+///
+/// Chapter 9's `Clamp` makes the same guarantee by silently fixing bad input. Here we reject it,
+/// which is usually better when the input comes from a user who should be told about the mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Percentage(u8);
+
+impl Percentage {
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+/// The error when a number is too big to be a percentage. It keeps the bad value so that the
+/// message can say what it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentageError {
+    value: u8,
+}
+
+impl Display for PercentageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "a percentage must be between 0 and 100, received '{}'",
+            self.value
+        )
+    }
+}
+
+impl Error for PercentageError {}
+
+/// `TryFrom` is the fallible version of `From`. Implementing it also gives us `TryInto`, so both
+/// `Percentage::try_from(50)` and `let p: Percentage = 50.try_into()?` work.
+impl TryFrom<u8> for Percentage {
+    type Error = PercentageError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > 100 {
+            Err(PercentageError { value })
+        } else {
+            Ok(Percentage(value))
+        }
+    }
+}
+
+impl Display for Percentage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hundred_is_ok() {
+        assert_eq!(100, Percentage::try_from(100).unwrap().value());
+    }
+
+    #[test]
+    fn zero_is_ok() {
+        assert_eq!(0, Percentage::try_from(0).unwrap().value());
+    }
+
+    #[test]
+    fn over_hundred_is_an_error() {
+        let err = Percentage::try_from(101).unwrap_err();
+        assert_eq!(
+            "a percentage must be between 0 and 100, received '101'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn try_into() {
+        let result: Result<Percentage, _> = 250u8.try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("42%", Percentage::try_from(42).unwrap().to_string());
+    }
+}
@@ -0,0 +1,20 @@
+// A copy of `NotObjectSafe` from `src/object_safety.rs`. A trait with a generic method can't be
+// made into a trait object, because the vtable would need an entry for every `T` anyone might
+// ever call `process` with.
+use std::fmt::Debug;
+
+trait NotObjectSafe {
+    fn process<T: Debug>(&self, t: T) -> String;
+}
+
+struct Printer;
+
+impl NotObjectSafe for Printer {
+    fn process<T: Debug>(&self, t: T) -> String {
+        format!("{:?}", t)
+    }
+}
+
+fn main() {
+    let boxed: Box<dyn NotObjectSafe> = Box::new(Printer);
+}
@@ -54,7 +54,7 @@ const EMPTY_STRING: String = String::new();
 ///
 /// Surprise! Rust allows you to blow away the existance of a variable and replace it with one that
 /// has the same name.
-fn shadowing() {
+fn shadowing() -> (i32, String) {
     let x = 1;
 
     // Does not compile.
@@ -71,6 +71,37 @@ fn shadowing() {
     // No problem! We can the type because the `let` keyword blows away the previous existence of
     // the variable, just as if it were out of scope.
     let y = String::from("Hello World");
+
+    (x, y)
+}
+
+/// Shadowing in an inner scope only lasts until the end of that scope. This is the example from the
+/// book. Returns the inner value and the outer value: `(12, 6)`.
+fn shadowing_in_scope() -> (i32, i32) {
+    let x = 5;
+    let x = x + 1;
+
+    let inner;
+    {
+        // This `x` shadows the outer `x` only inside these braces.
+        let x = x * 2;
+        inner = x;
+    }
+
+    // The outer `x` was never changed, the inner one is gone.
+    (inner, x)
+}
+
+/// Shadowing lets us change the type while keeping the name, which is handy when a value goes
+/// through a few forms. Here `input` is a `&str`, then a `u32`, then a `String`, and we return the
+/// number of digits in the parsed number. Input that is not a number is treated as `0`.
+///
+/// With `mut` this would not compile because a `mut` variable can't change type.
+fn parse_then_shadow(input: &str) -> usize {
+    let input = input.trim();
+    let input: u32 = input.parse().unwrap_or(0);
+    let input = input.to_string();
+    input.len()
 }
 
 /// Section 3.2
@@ -137,6 +168,56 @@ fn statements_and_expressions(decide: i32) -> i32 {
     x
 }
 
+/// A tiny calculator where almost everything is an expression. The `match` is the last expression in
+/// the function so its value is returned, and each arm evaluates to an `Option<i32>`:
+/// - `+`, `-` and `*` use the `checked_` functions, which return `None` on overflow.
+/// - `/` uses an `if` expression, both branches evaluate to a value.
+/// - `^` uses a block expression containing a `loop` that returns a value with `break`.
+///
+/// Returns `None` for an unknown operator, division by zero, a negative exponent or overflow.
+fn evaluate(op: char, a: i32, b: i32) -> Option<i32> {
+    match op {
+        '+' => a.checked_add(b),
+        '-' => a.checked_sub(b),
+        '*' => a.checked_mul(b),
+        '/' => {
+            if b == 0 {
+                None
+            } else {
+                a.checked_div(b)
+            }
+        }
+        '^' => {
+            let mut result: i32 = 1;
+            let mut remaining = b;
+            // `break` with a value makes the whole `loop` evaluate to that value.
+            loop {
+                if remaining < 0 {
+                    break None;
+                }
+                if remaining == 0 {
+                    break Some(result);
+                }
+                // For `a` of 0, 1 or -1, `checked_mul` never overflows, so without these checks a
+                // big `b` would take billions of trips around the loop. Powers of -1 just flip
+                // between 1 and -1, so whether `remaining` is odd or even gives the answer.
+                if a == -1 {
+                    break Some(if remaining % 2 == 0 { result } else { -result });
+                }
+                let next = result.checked_mul(a)?;
+                remaining -= 1;
+                // Once multiplying by `a` stops changing the result (`a` is 1, or the result has
+                // reached 0), it never will again.
+                if next == result {
+                    break Some(result);
+                }
+                result = next;
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Section 3.4 discusses comments.
 /// https://doc.rust-lang.org/book/ch03-04-comments.html#comments
 ///
@@ -153,3 +234,96 @@ const NO_DOCUMENTATION: () = ();
 /// Then extra note here: if-let
 /// https://doc.rust-lang.org/book/ch03-04-comments.html#comments
 fn foo() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadowing_changes_value_and_type() {
+        let (x, y) = shadowing();
+        assert_eq!(2, x);
+        assert_eq!("Hello World", y);
+    }
+
+    #[test]
+    fn shadowing_in_scope_does_not_leak() {
+        let (inner, outer) = shadowing_in_scope();
+        assert_eq!(12, inner);
+        assert_eq!(6, outer);
+    }
+
+    #[test]
+    fn parse_then_shadow_two_digits() {
+        assert_eq!(2, parse_then_shadow("42"));
+    }
+
+    #[test]
+    fn parse_then_shadow_trims_whitespace() {
+        assert_eq!(4, parse_then_shadow("  1000\n"));
+    }
+
+    #[test]
+    fn parse_then_shadow_not_a_number() {
+        assert_eq!(1, parse_then_shadow("forty-two"));
+    }
+
+    #[test]
+    fn evaluate_add() {
+        assert_eq!(Some(5), evaluate('+', 2, 3));
+    }
+
+    #[test]
+    fn evaluate_subtract() {
+        assert_eq!(Some(-1), evaluate('-', 2, 3));
+    }
+
+    #[test]
+    fn evaluate_multiply() {
+        assert_eq!(Some(6), evaluate('*', 2, 3));
+    }
+
+    #[test]
+    fn evaluate_divide() {
+        assert_eq!(Some(3), evaluate('/', 7, 2));
+    }
+
+    #[test]
+    fn evaluate_divide_by_zero() {
+        assert_eq!(None, evaluate('/', 7, 0));
+    }
+
+    #[test]
+    fn evaluate_power() {
+        assert_eq!(Some(8), evaluate('^', 2, 3));
+    }
+
+    #[test]
+    fn evaluate_power_of_zero() {
+        assert_eq!(Some(1), evaluate('^', 5, 0));
+    }
+
+    #[test]
+    fn evaluate_negative_power() {
+        assert_eq!(None, evaluate('^', 2, -1));
+    }
+
+    #[test]
+    fn evaluate_huge_power_of_small_numbers() {
+        assert_eq!(Some(1), evaluate('^', 1, i32::MAX));
+        assert_eq!(Some(0), evaluate('^', 0, i32::MAX));
+        assert_eq!(Some(-1), evaluate('^', -1, i32::MAX));
+        assert_eq!(Some(1), evaluate('^', -1, i32::MAX - 1));
+        assert_eq!(Some(-8), evaluate('^', -2, 3));
+    }
+
+    #[test]
+    fn evaluate_overflow() {
+        assert_eq!(None, evaluate('*', i32::MAX, 2));
+    }
+
+    #[test]
+    fn evaluate_unknown_operator() {
+        assert_eq!(None, evaluate('%', 7, 2));
+    }
+}
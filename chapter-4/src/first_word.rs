@@ -0,0 +1,42 @@
+/// The book's improved version of `first_word` (see `slice_types` in `main.rs`) returns a slice,
+/// which is a reference into the input. The returned `&str` borrows from `s`, so the compiler won't
+/// let anyone change `s` while the slice is in use (see `tests/ui/mutate_while_slice_alive.rs`).
+/// The stale index bug in `slice_types::stale_index` becomes a compile error.
+///
+/// Taking `&str` instead of `&String` means it works with string literals too.
+pub fn first_word_slice(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[..i];
+        }
+    }
+
+    s
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_word_slice_of_sentence() {
+        let s = String::from("hello world");
+        assert_eq!("hello", first_word_slice(&s));
+    }
+
+    #[test]
+    fn first_word_slice_of_single_word() {
+        assert_eq!("hello", first_word_slice("hello"));
+    }
+
+    #[test]
+    fn first_word_slice_of_empty_string() {
+        assert_eq!("", first_word_slice(""));
+    }
+}
@@ -1,5 +1,7 @@
 #![allow(dead_code, unused_variables, unused_mut)]
 
+mod first_word;
+
 fn main() {
     println!("Chapter 4!");
     slice_types::show_me_slices();
@@ -62,11 +64,152 @@ mod what_is_ownership {
     /// What is special about these primitives that make them so quick to copy?
     /// Enter the `Copy` trait.
     fn memory_and_allocation() {
-        // TODO - demo Clone and Copy if there's time
+        copy_and_clone::show_copy_and_clone();
     }
 
-    // Take a look at these two programs and talk through them:
-    // https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html#return-values-and-scope
+    /// # Copy and Clone
+    ///
+    /// `Clone` is an explicit, possibly expensive, deep copy: you have to call `.clone()`.
+    /// `Copy` is an implicit, cheap, bit-for-bit copy that happens instead of a move. A type can
+    /// only be `Copy` if all of its fields are `Copy`, so nothing that owns heap memory (like a
+    /// `String`) can be `Copy`. Every `Copy` type must also be `Clone`.
+    mod copy_and_clone {
+        /// All fields are `Copy`, so the whole struct can be `Copy`.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub(super) struct Point {
+            pub(super) x: i32,
+            pub(super) y: i32,
+        }
+
+        /// `String` is not `Copy`, so this struct can only be `Clone`.
+        #[derive(Debug, Clone, PartialEq)]
+        pub(super) struct Person {
+            pub(super) name: String,
+        }
+
+        pub(super) fn show_copy_and_clone() {
+            let p1 = Point { x: 1, y: 2 };
+            // This is a copy, not a move. `p1` is still usable.
+            let p2 = p1;
+            println!("p1: {:?}, p2: {:?}", p1, p2);
+
+            let person1 = Person {
+                name: String::from("Ferris"),
+            };
+            // This would be a move, and `person1` would no longer be usable:
+            // let person2 = person1;
+            // println!("{:?}", person1); // PROBLEM: borrow of moved value
+            //
+            // Instead we have to ask for a copy explicitly. This allocates a new `String`.
+            let person2 = person1.clone();
+            println!("person1: {:?}, person2: {:?}", person1, person2);
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn copy_leaves_original_usable() {
+                let p1 = Point { x: 1, y: 2 };
+                let mut p2 = p1;
+                p2.x = 10;
+                // `p2` is an independent copy, so changing it does not change `p1`.
+                assert_eq!(Point { x: 1, y: 2 }, p1);
+                assert_eq!(Point { x: 10, y: 2 }, p2);
+            }
+
+            #[test]
+            fn clone_is_explicit_and_deep() {
+                let person1 = Person {
+                    name: String::from("Ferris"),
+                };
+                let mut person2 = person1.clone();
+                person2.name.push_str(" the Crab");
+                assert_eq!("Ferris", person1.name);
+                assert_eq!("Ferris the Crab", person2.name);
+                // The clone has its own heap allocation.
+                assert_ne!(person1.name.as_ptr(), person2.name.as_ptr());
+            }
+
+            #[test]
+            fn move_without_clone() {
+                let person1 = Person {
+                    name: String::from("Ferris"),
+                };
+                let ptr = person1.name.as_ptr();
+                // A move does not copy the heap data, the new owner points at the same buffer.
+                let person2 = person1;
+                assert_eq!(ptr, person2.name.as_ptr());
+            }
+        }
+    }
+
+    /// # Return Values and Scope
+    /// https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html#return-values-and-scope
+    ///
+    /// Returning a value moves ownership out to the caller, just like passing an argument moves
+    /// ownership in. Talk through where each `String` is moved and where it is dropped.
+    mod return_values_and_scope {
+        /// Moves its return value into the function that calls it.
+        pub(super) fn gives_ownership() -> String {
+            // `some_string` comes into scope...
+            let some_string = String::from("yours");
+            // ...and is moved out to the caller. It is not dropped here.
+            some_string
+        }
+
+        /// Takes a `String` and returns one. Ownership moves in, then moves back out.
+        pub(super) fn takes_and_gives_back(a_string: String) -> String {
+            // `a_string` comes into scope, and is returned (moved) to the caller.
+            a_string
+        }
+
+        /// Reproduces the program from the book, but returns `s1` and `s3` so that we can test them.
+        pub(super) fn return_values_and_scope() -> (String, String) {
+            // `gives_ownership` moves its return value into `s1`.
+            let s1 = gives_ownership();
+
+            // `s2` comes into scope.
+            let s2 = String::from("hello");
+
+            // `s2` is moved into `takes_and_gives_back`, which moves it back out into `s3`. `s2`
+            // can't be used after this line, but its heap data lives on in `s3`.
+            let s3 = takes_and_gives_back(s2);
+            // println!("{}", s2); // PROBLEM: borrow of moved value
+
+            // `s1` and `s3` are moved out to our caller. If we didn't return them, they would be
+            // dropped here. `s2` was moved, so nothing happens to it.
+            (s1, s3)
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn gives_ownership_returns_yours() {
+                assert_eq!("yours", gives_ownership());
+            }
+
+            #[test]
+            fn takes_and_gives_back_returns_the_same_string() {
+                let s = String::from("hello");
+                let ptr = s.as_ptr();
+                let returned = takes_and_gives_back(s);
+                assert_eq!("hello", returned);
+                // It's the same heap buffer. Moving in and out did not copy anything.
+                assert_eq!(ptr, returned.as_ptr());
+            }
+
+            #[test]
+            fn return_values_and_scope_moves_out() {
+                let (s1, s3) = return_values_and_scope();
+                assert_eq!("yours", s1);
+                assert_eq!("hello", s3);
+            }
+        }
+    }
 
     // Take a look at this program and talk through it:
     // https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html#ownership-and-functions
@@ -227,6 +370,18 @@ mod slice_types {
         s.len()
     }
 
+    /// The problem with `first_word` is that the index has nothing to do with the `String` after
+    /// it is returned. If the string changes, the index is stale and nothing tells us so.
+    fn stale_index() {
+        let mut s = String::from("hello world");
+        let word = first_word(&s); // 5
+        s.clear();
+        // `word` is still 5, but `s` is empty. Using it to slice `s` would panic.
+    }
+
+    // The book's improved version, `first_word_slice`, is in `first_word.rs` so that the test in
+    // `tests/ui` can include it.
+
     /// Why are slices useful?
     pub(crate) fn show_me_slices() {
         let s = String::from("hello world");
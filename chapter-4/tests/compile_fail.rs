@@ -0,0 +1,15 @@
+/// These programs are expected to fail to compile. `trybuild` compiles each one and compares the
+/// compiler's output to the `.stderr` file next to it.
+///
+/// Every crate with a `tests/ui` directory has a copy of this harness, which points back here.
+/// When the crate is a binary, a program in `tests/ui` can't `use` it. Instead it includes the
+/// module it tests with `#[path = "../../src/<module>.rs"] mod <module>;`, so that it checks the
+/// real code rather than a copy of it.
+///
+/// If the compiler's wording changes, regenerate the `.stderr` files with:
+/// `TRYBUILD=overwrite cargo test -p <crate> --test compile_fail`, e.g. `-p chapter-4`.
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
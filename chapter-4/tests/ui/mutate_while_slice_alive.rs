@@ -0,0 +1,13 @@
+// `chapter-4` is a binary so we can't `use` it here, but we can include the module's source.
+#[path = "../../src/first_word.rs"]
+mod first_word;
+
+use first_word::first_word_slice;
+
+fn main() {
+    let mut s = String::from("hello world");
+    let word = first_word_slice(&s);
+    // DOES NOT COMPILE: `word` borrows `s`, so `s` can't be mutably borrowed by `clear`.
+    s.clear();
+    println!("the first word is: {}", word);
+}
@@ -0,0 +1,79 @@
+use std::fmt::{Debug, Formatter};
+
+/// `#[derive(Debug)]` prints every field, which is exactly what we want most of the time. But this
+/// struct would print the password into any log message or panic that includes it with `{:?}`.
+#[derive(Debug)]
+pub struct LeakyCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// The same struct with a hand-written `Debug` that hides the password.
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// `Formatter::debug_struct` is a builder that produces the same output as the derive macro,
+/// including the pretty-printed form for `{:#?}`, but lets us decide what each field looks like.
+/// There are also `debug_tuple`, `debug_list`, `debug_set` and `debug_map` builders.
+///
+/// `finish_non_exhaustive` (instead of `finish`) would print `..` to show that fields were left
+/// out. Here we keep the field but replace its value.
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            // `format_args!` has a `Debug` impl that prints without the quotes a `&str` would get.
+            .field("password", &format_args!("***"))
+            .finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_debug_leaks_password() {
+        let credentials = LeakyCredentials {
+            username: "ferris".to_string(),
+            password: "hunter2".to_string(),
+        };
+        assert_eq!(
+            r#"LeakyCredentials { username: "ferris", password: "hunter2" }"#,
+            format!("{:?}", credentials)
+        );
+    }
+
+    #[test]
+    fn manual_debug_redacts_password() {
+        let credentials = Credentials {
+            username: "ferris".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let debug = format!("{:?}", credentials);
+        assert_eq!(
+            r#"Credentials { username: "ferris", password: *** }"#,
+            debug
+        );
+        assert!(!debug.contains("hunter2"));
+    }
+
+    #[test]
+    fn manual_debug_pretty() {
+        let credentials = Credentials {
+            username: "ferris".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let expected = r#"Credentials {
+    username: "ferris",
+    password: ***,
+}"#;
+        assert_eq!(expected, format!("{:#?}", credentials));
+    }
+}
@@ -7,11 +7,21 @@ pp. 83-96 in the 2018 edition paperback.
  */
 #![allow(dead_code, unused_variables, unused_mut)]
 
+use crate::money::Money;
+use crate::table::Table;
 use std::fmt::{Display, Formatter};
 
+mod credentials;
+mod money;
+mod server_config;
+mod source_file;
+mod table;
+
 fn main() {
     println!("Chapter 5!");
     use_the_empty_struct();
+    print_a_table();
+    print_some_money();
 }
 
 /// ```
@@ -114,6 +124,8 @@ struct SuperUser {
 //     }
 // }
 
+// Struct update syntax is often used with `..Default::default()`. See `server_config.rs`.
+
 // Tuple Structs
 // [p. 86](https://doc.rust-lang.org/book/ch05-01-defining-structs.html#using-tuple-structs-without-named-fields-to-create-different-types)
 
@@ -216,6 +228,27 @@ struct PrintMe {
 //     println!("{:?}", value);
 // }
 
+// A manual `Debug` impl can also leave things out. See `credentials.rs` for one that uses
+// `Formatter::debug_struct` to hide a password.
+
+/// `Display` can write as many lines as it likes. See `table.rs` for a struct that displays itself
+/// as a table with the columns lined up.
+fn print_a_table() {
+    let table = Table::new(vec![
+        vec!["Struct".to_string(), "Page".to_string()],
+        vec!["User".to_string(), "84".to_string()],
+        vec!["Rectangle".to_string(), "90".to_string()],
+    ]);
+    println!("{}", table);
+}
+
+/// A `Display` impl has to opt in to the width and alignment flags. See `money.rs`.
+fn print_some_money() {
+    for cents in [150, -2599, 100000] {
+        println!("[{:>12}]", Money::new(cents));
+    }
+}
+
 // Different selfs
 
 pub struct WrappedString {
@@ -224,6 +257,9 @@ pub struct WrappedString {
 
 impl WrappedString {
     /// Capitalized `Self` represent the type, i.e. `WrappedString`.
+    ///
+    /// `Into<String>` lets the caller pass a `&str` or a `String`. See `source_file.rs` for the
+    /// `AsRef<Path>` version of this idea, and how `AsRef` differs from `Into`.
     pub fn new<S: Into<String>>(value: S) -> Self {
         Self {
             value: value.into(),
@@ -0,0 +1,107 @@
+use std::fmt::{Alignment, Display, Formatter, Write};
+
+/// An amount of money in cents. Storing cents in an integer rather than dollars in a float means we
+/// never have to worry about rounding errors like `0.1 + 0.2 != 0.3`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Money {
+    pub cents: i64,
+}
+
+impl Money {
+    pub fn new(cents: i64) -> Self {
+        Self { cents }
+    }
+}
+
+/// Prints like `$1.50` or `-$1.50` and honors the width, fill and alignment that the caller asked
+/// for, e.g. `format!("{:>10}", money)` or `format!("{:*<10}", money)`.
+///
+/// The `Formatter` carries the flags from the format string. If we wrote the text with `write!`
+/// they would be silently ignored, which is a common surprise with hand-written `Display` impls.
+/// `Formatter::pad` would handle them for us, but it also treats a precision like `{:.2}` as "cut
+/// the string off after 2 characters", so here we do the padding ourselves to show how the flags
+/// work. Like the numbers in `std`, we align right unless asked otherwise.
+impl Display for Money {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        // `unsigned_abs` can't overflow, even for `i64::MIN`.
+        let cents = self.cents.unsigned_abs();
+        let text = format!("{}${}.{:02}", sign, cents / 100, cents % 100);
+
+        let len = text.chars().count();
+        let padding = f.width().unwrap_or(0).saturating_sub(len);
+        let (before, after) = match f.align() {
+            Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(Alignment::Right) | None => (padding, 0),
+        };
+
+        for _ in 0..before {
+            f.write_char(f.fill())?;
+        }
+        f.write_str(&text)?;
+        for _ in 0..after {
+            f.write_char(f.fill())?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_decimal_places() {
+        assert_eq!("$1.50", Money::new(150).to_string());
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!("$0.00", Money::new(0).to_string());
+    }
+
+    #[test]
+    fn negative() {
+        assert_eq!("-$1.50", Money::new(-150).to_string());
+    }
+
+    #[test]
+    fn negative_cents_only() {
+        assert_eq!("-$0.05", Money::new(-5).to_string());
+    }
+
+    #[test]
+    fn right_aligned() {
+        assert_eq!("     $1.50", format!("{:>10}", Money::new(150)));
+    }
+
+    #[test]
+    fn right_aligned_by_default() {
+        assert_eq!("    -$1.50", format!("{:10}", Money::new(-150)));
+    }
+
+    #[test]
+    fn left_aligned_with_fill() {
+        assert_eq!("$1.50*****", format!("{:*<10}", Money::new(150)));
+    }
+
+    #[test]
+    fn centered() {
+        assert_eq!("  $0.00   ", format!("{:^10}", Money::new(0)));
+    }
+
+    #[test]
+    fn width_smaller_than_text() {
+        assert_eq!("$1234.56", format!("{:>3}", Money::new(123456)));
+    }
+
+    #[test]
+    fn minimum() {
+        assert_eq!("-$92233720368547758.08", Money::new(i64::MIN).to_string());
+    }
+}
@@ -0,0 +1,82 @@
+/// When every field's own default is what we want (`0`, `false`, an empty `String`, `None`...) the
+/// derive macro is all we need.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogOptions {
+    pub verbose: bool,
+    pub file: Option<String>,
+}
+
+/// A derived `Default` would give us a `port` of `0` and an empty `host`, which are not useful
+/// defaults for a server. When the defaults are anything other than the field types' defaults we
+/// have to implement `Default` by hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub max_connections: usize,
+    pub log: LogOptions,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 80,
+            max_connections: 100,
+            // The fields we don't care about can still use their own `Default`.
+            log: LogOptions::default(),
+        }
+    }
+}
+
+/// Struct update syntax works with any instance, not just an existing variable like `..user`.
+/// Combined with `Default` it means "these fields, and the defaults for everything else". The
+/// caller doesn't have to change when we add a field to `ServerConfig`, as long as it has a default.
+pub fn dev_server() -> ServerConfig {
+    ServerConfig {
+        port: 8080,
+        ..Default::default()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_values() {
+        let config = ServerConfig::default();
+        assert_eq!("localhost", config.host);
+        assert_eq!(80, config.port);
+        assert_eq!(100, config.max_connections);
+        assert_eq!(LogOptions::default(), config.log);
+    }
+
+    #[test]
+    fn override_port_only() {
+        let config = dev_server();
+        assert_eq!(8080, config.port);
+        assert_eq!("localhost", config.host);
+        assert_eq!(100, config.max_connections);
+        assert!(!config.log.verbose);
+        assert_eq!(None, config.log.file);
+    }
+
+    #[test]
+    fn override_nested() {
+        let config = ServerConfig {
+            log: LogOptions {
+                verbose: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(config.log.verbose);
+        assert_eq!(None, config.log.file);
+        assert_eq!(80, config.port);
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+/// A path to a source file, to show a constructor that takes `impl AsRef<Path>` like
+/// `std::fs::read_to_string` and `std::fs::File::open` do.
+///
+/// `WrappedString::new` takes `S: Into<String>`. `Into` *converts*: it consumes the argument, so a
+/// `String` is moved in for free and a `&str` is copied into a new `String`. That's what we want
+/// when the struct is going to own the value anyway.
+///
+/// `AsRef` *borrows*: it lets us look at the argument as a `&Path` without taking it. `&str`,
+/// `String`, `PathBuf`, `&Path` and `OsString` all implement `AsRef<Path>`, so callers can pass
+/// whatever they have. It's ideal for functions that only read the path. Here we want to keep the
+/// path, so we have to copy it with `to_path_buf`, even if the caller gave us a `PathBuf` they
+/// didn't need anymore. `impl Into<PathBuf>` would avoid that copy, but `AsRef<Path>` is what you
+/// will see most often in APIs that take paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    path: PathBuf,
+}
+
+impl SourceFile {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// A `.rs` file is a Rust file.
+    pub fn is_rust(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext == "rs")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_from_str() {
+        let file = SourceFile::new("src/main.rs");
+        assert_eq!(Path::new("src/main.rs"), file.path());
+        assert!(file.is_rust());
+    }
+
+    #[test]
+    fn new_from_string() {
+        let file = SourceFile::new(String::from("Cargo.toml"));
+        assert_eq!(Path::new("Cargo.toml"), file.path());
+        assert!(!file.is_rust());
+    }
+
+    #[test]
+    fn new_from_path_buf() {
+        let file = SourceFile::new(PathBuf::from("src").join("table.rs"));
+        assert_eq!(Path::new("src/table.rs"), file.path());
+        assert!(file.is_rust());
+    }
+
+    #[test]
+    fn new_from_borrowed_path_buf() {
+        let path = PathBuf::from("src/money.rs");
+        let file = SourceFile::new(&path);
+        // We only borrowed `path`, so we can still use it.
+        assert_eq!(path, file.path());
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::{Display, Formatter};
+
+/// A table of text where the first row is the header. `Display` lines the columns up, e.g.
+///
+/// ```text
+/// | Name  | Age |
+/// |-------|-----|
+/// | Alice | 30  |
+/// | Bob   |     |
+/// ```
+///
+/// Rows don't all need the same number of cells. Missing cells at the end of a short row are
+/// printed as empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(rows: Vec<Vec<String>>) -> Self {
+        Self { rows }
+    }
+
+    /// The number of columns is the length of the longest row.
+    fn column_count(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    /// The width of each column is the width of its widest cell. We count `char`s rather than bytes
+    /// because the padding in `write!` is counted in `char`s too.
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths = vec![0; self.column_count()];
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        widths
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let widths = self.column_widths();
+
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "|")?;
+            for (column, width) in widths.iter().enumerate() {
+                let cell = row.get(column).map(String::as_str).unwrap_or("");
+                // `width$` takes the width from a named argument, `<` left-aligns.
+                write!(f, " {:<width$} |", cell, width = width)?;
+            }
+
+            // Separate the header from the body.
+            if i == 0 {
+                writeln!(f)?;
+                write!(f, "|")?;
+                for width in &widths {
+                    write!(f, "{}|", "-".repeat(width + 2))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(rows: &[&[&str]]) -> Table {
+        Table::new(
+            rows.iter()
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn aligned_with_missing_cell() {
+        let table = table(&[&["Name", "Age"], &["Alice", "30"], &["Bob"]]);
+        let expected = "\
+| Name  | Age |
+|-------|-----|
+| Alice | 30  |
+| Bob   |     |";
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn header_only() {
+        let table = table(&[&["Name", "Age"]]);
+        let expected = "\
+| Name | Age |
+|------|-----|";
+        assert_eq!(expected, table.to_string());
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!("", Table::default().to_string());
+    }
+}
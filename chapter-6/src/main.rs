@@ -43,24 +43,86 @@ mod enums_with_and_without_data {
         UnnamedStruct { x: u64, y: u64 },
     }
 
+    fn example() {
+        let value_1 = DataEnum::Empty;
+        let value_2 = DataEnum::Point(Point { x: 0, y: 1 });
+        let value_3 = DataEnum::UnnamedStruct { x: 0, y: 1 };
+    }
+
+    /// This is where enums as *algebraic data types* pay off. An expression is either a number, or
+    /// an operation on two other expressions. The enum refers to itself, so the inner expressions
+    /// have to be boxed, otherwise `Expr` would have an infinite size.
+    #[derive(Debug, Clone, PartialEq)]
+    enum Expr {
+        Num(f64),
+        Add(Box<Expr>, Box<Expr>),
+        Mul(Box<Expr>, Box<Expr>),
+    }
+
     // You can implement functions on enums!
-    impl DataEnum {
-        pub fn explode(&self) {
+    impl Expr {
+        /// Evaluates the expression by matching on the variant and recursing into the boxed
+        /// sub-expressions. The `match` is exhaustive, so adding a variant (say `Sub`) won't compile
+        /// until `eval` handles it.
+        pub fn eval(&self) -> f64 {
             match self {
-                DataEnum::Empty => todo!("Empty"),
-                DataEnum::Point(Point { x: 0, .. }) => todo!("x is zero"),
-                DataEnum::UnnamedStruct { x: 0, .. } => todo!("Unnamed x is zero"),
-                _ => todo!("Sometime else"),
+                Expr::Num(n) => *n,
+                Expr::Add(left, right) => left.eval() + right.eval(),
+                Expr::Mul(left, right) => left.eval() * right.eval(),
             }
         }
     }
 
-    fn example() {
-        let value_1 = DataEnum::Empty;
-        let value_2 = DataEnum::Point(Point { x: 0, y: 1 });
-        let value_3 = DataEnum::UnnamedStruct { x: 0, y: 1 };
+    fn use_expr() {
+        use Expr::{Add, Mul, Num};
+
+        // 1 + 2 * 3
+        let expr = Add(
+            Box::new(Num(1.0)),
+            Box::new(Mul(Box::new(Num(2.0)), Box::new(Num(3.0)))),
+        );
+        println!("{:?} = {}", expr, expr.eval());
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::Expr::{Add, Mul, Num};
+
+        #[test]
+        fn eval_num() {
+            assert_eq!(4.5, Num(4.5).eval());
+        }
 
-        value_3.explode();
+        #[test]
+        fn eval_add() {
+            let expr = Add(Box::new(Num(1.0)), Box::new(Num(2.0)));
+            assert_eq!(3.0, expr.eval());
+        }
+
+        #[test]
+        fn eval_mul() {
+            let expr = Mul(Box::new(Num(2.0)), Box::new(Num(3.0)));
+            assert_eq!(6.0, expr.eval());
+        }
+
+        #[test]
+        fn eval_nested() {
+            let expr = Add(
+                Box::new(Num(1.0)),
+                Box::new(Mul(Box::new(Num(2.0)), Box::new(Num(3.0)))),
+            );
+            assert_eq!(7.0, expr.eval());
+        }
+
+        #[test]
+        fn eval_nested_on_the_left() {
+            // (1 + 2) * 3
+            let expr = Mul(
+                Box::new(Add(Box::new(Num(1.0)), Box::new(Num(2.0)))),
+                Box::new(Num(3.0)),
+            );
+            assert_eq!(9.0, expr.eval());
+        }
     }
 }
 
@@ -86,6 +148,92 @@ fn option_unwraps() {
     });
 }
 
+/// Option combinators. Instead of unwrapping, we can transform the value inside the `Option` and
+/// let `None` pass through untouched. Each of these returns a new `Option` (or `Result`) so they
+/// chain together.
+///
+/// - `map`: change the value if there is one.
+/// - `and_then`: change the value with a function that might itself return `None`.
+/// - `filter`: turn `Some` into `None` if the value doesn't pass a test.
+/// - `ok_or`: turn an `Option` into a `Result` by saying what the error is for `None`.
+mod option_combinators {
+    /// `parse` returns a `Result` and `ok()` turns it into an `Option`. If parsing worked,
+    /// `and_then` doubles the number. Doubling can overflow (`"2000000000"` parses, but twice that
+    /// is too big for an `i32`), so we use `checked_mul`, which returns an `Option` too.
+    /// `map(|n| n * 2)` would panic in a debug build.
+    pub(super) fn parse_and_double(s: &str) -> Option<i32> {
+        s.parse::<i32>().ok().and_then(|n| n.checked_mul(2))
+    }
+
+    /// `filter` throws away a zero divisor, `and_then` does the division, which can still fail for
+    /// `i32::MIN / -1`.
+    pub(super) fn safe_div(a: i32, b: i32) -> Option<i32> {
+        Some(b).filter(|&b| b != 0).and_then(|b| a.checked_div(b))
+    }
+
+    /// `ok_or` gives a `None` an error message so that the caller knows why it failed.
+    pub(super) fn first_doubled(list: &[&str]) -> Result<i32, &'static str> {
+        list.first()
+            .ok_or("the list is empty")
+            .and_then(|s| parse_and_double(s).ok_or("not a number, or too big to double"))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parse_and_double_valid() {
+            assert_eq!(Some(42), parse_and_double("21"));
+        }
+
+        #[test]
+        fn parse_and_double_invalid() {
+            assert_eq!(None, parse_and_double("twenty-one"));
+        }
+
+        #[test]
+        fn parse_and_double_overflow() {
+            assert_eq!(None, parse_and_double(&i32::MAX.to_string()));
+            assert_eq!(None, parse_and_double("2000000000"));
+            assert_eq!(Some(-2000000000), parse_and_double("-1000000000"));
+        }
+
+        #[test]
+        fn safe_div_valid() {
+            assert_eq!(Some(3), safe_div(7, 2));
+        }
+
+        #[test]
+        fn safe_div_by_zero() {
+            assert_eq!(None, safe_div(7, 0));
+        }
+
+        #[test]
+        fn safe_div_overflow() {
+            assert_eq!(None, safe_div(i32::MIN, -1));
+        }
+
+        #[test]
+        fn first_doubled_valid() {
+            assert_eq!(Ok(2), first_doubled(&["1", "2"]));
+        }
+
+        #[test]
+        fn first_doubled_empty() {
+            assert_eq!(Err("the list is empty"), first_doubled(&[]));
+        }
+
+        #[test]
+        fn first_doubled_invalid() {
+            assert_eq!(
+                Err("not a number, or too big to double"),
+                first_doubled(&["one"])
+            );
+        }
+    }
+}
+
 enum Letter {
     A,
     B,
@@ -76,3 +76,129 @@ mod rename_a_trait {
         x.foo();
     }
 }
+
+/// # Re-exporting with `pub use`
+///
+/// Libraries often organize their code in deep module trees that make sense to the authors, but
+/// users shouldn't have to know about them. A `pub use` at the crate root re-exports the item so
+/// that users can write `e_pub_vs_private::Thing` instead of
+/// `e_pub_vs_private::deep::nested::Thing`. Both paths name the same type.
+///
+/// The standard library does this all over the place, e.g. `std::collections::HashMap` really
+/// lives in `std::collections::hash::map`.
+pub use deep::nested::Thing;
+
+pub mod deep {
+    pub mod nested {
+        #[derive(Debug, Default, Clone, PartialEq, Eq)]
+        pub struct Thing {
+            pub name: String,
+        }
+
+        impl Thing {
+            pub fn new(name: impl Into<String>) -> Self {
+                Self { name: name.into() }
+            }
+        }
+    }
+}
+
+/// # Access Levels
+///
+/// The functions in `inner` have every visibility we can choose from. Who can call them?
+/// - `pub`: anyone, including other crates.
+/// - `pub(crate)`: anyone in this crate, but not other crates.
+/// - `pub(super)`: the parent module (`outer`) and its descendants.
+/// - private: the module itself (`inner`) and its descendants.
+///
+/// The `tests/ui` directory has programs that try to break these rules and fail to compile.
+pub mod access {
+    pub mod outer {
+        pub mod inner {
+            pub fn public() -> &'static str {
+                "pub"
+            }
+
+            pub(crate) fn crate_only() -> &'static str {
+                "pub(crate)"
+            }
+
+            pub(super) fn parent_only() -> &'static str {
+                "pub(super)"
+            }
+
+            fn private() -> &'static str {
+                "private"
+            }
+
+            /// A module can always use its own private items.
+            pub fn calls_private() -> &'static str {
+                private()
+            }
+        }
+
+        /// `outer` is the parent of `inner` so it can call the `pub(super)` function.
+        pub fn calls_parent_only() -> &'static str {
+            inner::parent_only()
+        }
+    }
+
+    /// `sibling` is next to `outer`, not inside of it.
+    pub mod sibling {
+        use super::outer::inner;
+
+        pub fn calls_public() -> &'static str {
+            inner::public()
+        }
+
+        pub fn calls_crate_only() -> &'static str {
+            inner::crate_only()
+        }
+
+        // Does not compile: `pub(super)` only reaches `outer`, and we are not in `outer`.
+        // pub fn calls_parent_only() -> &'static str {
+        //     inner::parent_only()
+        // }
+
+        // Does not compile: `private` is only visible inside `inner`.
+        // pub fn calls_private() -> &'static str {
+        //     inner::private()
+        // }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::outer::{self, inner};
+        use super::sibling;
+
+        #[test]
+        fn public_from_anywhere() {
+            assert_eq!("pub", inner::public());
+        }
+
+        #[test]
+        fn crate_only_from_the_same_crate() {
+            assert_eq!("pub(crate)", inner::crate_only());
+        }
+
+        #[test]
+        fn parent_only_through_the_parent() {
+            assert_eq!("pub(super)", outer::calls_parent_only());
+        }
+
+        #[test]
+        fn private_through_its_own_module() {
+            assert_eq!("private", inner::calls_private());
+        }
+
+        #[test]
+        fn sibling_calls_public() {
+            assert_eq!("pub", sibling::calls_public());
+        }
+
+        #[test]
+        fn sibling_calls_crate_only() {
+            assert_eq!("pub(crate)", sibling::calls_crate_only());
+        }
+    }
+}
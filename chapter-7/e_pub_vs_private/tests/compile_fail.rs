@@ -0,0 +1,6 @@
+/// See `chapter-4/tests/compile_fail.rs` for how these tests work and how to update them.
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
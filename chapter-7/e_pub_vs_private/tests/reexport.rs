@@ -0,0 +1,18 @@
+//! Uses the library the way another crate would, through the short path that `pub use` creates.
+
+use e_pub_vs_private::Thing;
+use std::any::TypeId;
+
+#[test]
+fn short_path_is_the_same_type() {
+    assert_eq!(
+        TypeId::of::<Thing>(),
+        TypeId::of::<e_pub_vs_private::deep::nested::Thing>()
+    );
+}
+
+#[test]
+fn short_path_is_interchangeable() {
+    let thing: e_pub_vs_private::deep::nested::Thing = Thing::new("widget");
+    assert_eq!("widget", thing.name);
+}
@@ -0,0 +1,10 @@
+// This program is its own crate, so `pub(crate)` items in `e_pub_vs_private` are off limits.
+
+use e_pub_vs_private::access::outer::inner;
+
+fn main() {
+    // Fine.
+    println!("{}", inner::public());
+    // DOES NOT COMPILE: `crate_only` is `pub(crate)`.
+    println!("{}", inner::crate_only());
+}
@@ -0,0 +1,27 @@
+// The module tree from `access` in `src/lib.rs`, copied here because the rule we want to break
+// is about modules within a single crate.
+
+mod outer {
+    pub mod inner {
+        pub(super) fn parent_only() -> &'static str {
+            "pub(super)"
+        }
+
+        fn private() -> &'static str {
+            "private"
+        }
+    }
+}
+
+mod sibling {
+    use super::outer::inner;
+
+    pub fn calls_parent_only() -> &'static str {
+        // DOES NOT COMPILE: `sibling` can't see `parent_only`.
+        inner::parent_only()
+    }
+}
+
+fn main() {
+    println!("{}", sibling::calls_parent_only());
+}
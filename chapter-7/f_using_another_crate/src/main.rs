@@ -0,0 +1,10 @@
+//! A separate crate that depends on `c_multiple_crates`. The `bin` targets inside
+//! `c_multiple_crates` get to use its library for free, but any other crate has to list it under
+//! `[dependencies]` in `Cargo.toml`. Once it's there, everything that is `pub` in the library is
+//! available by its crate name.
+
+use c_multiple_crates::{greet, Greeting};
+
+fn main() {
+    greet(Greeting::Hello);
+}
@@ -0,0 +1,13 @@
+//! Runs the compiled binary and checks what it printed. Cargo builds the binary before running
+//! integration tests and tells us where it is with `CARGO_BIN_EXE_<name>`.
+
+use std::process::Command;
+
+#[test]
+fn prints_hello() {
+    let output = Command::new(env!("CARGO_BIN_EXE_f_using_another_crate"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!("Hello World!\n", String::from_utf8_lossy(&output.stdout));
+}
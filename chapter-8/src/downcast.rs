@@ -0,0 +1,72 @@
+use std::any::{type_name, Any};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The error when a `dyn Any` holds something other than the type we asked for.
+///
+/// It would be nice to say what the value actually was, but `dyn Any` can only tell us its
+/// `TypeId`, which is an opaque number. `type_name` works on a type parameter, not a value, so we
+/// can only name the type we *expected*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeMismatchError {
+    expected: &'static str,
+}
+
+impl TypeMismatchError {
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+}
+
+impl Display for TypeMismatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected a value of type '{}'", self.expected)
+    }
+}
+
+impl Error for TypeMismatchError {}
+
+/// `downcast_ref` returns an `Option`, and `multiple_types_in_vectors` just moves on when it is
+/// `None`. This turns the `None` into an error that says which type we were looking for, so that
+/// it can be passed up with `?`.
+///
+/// The `T: 'static` bound comes from `Any` itself: only types that don't borrow anything can be
+/// `Any`.
+///
+/// Note that `type_name` is meant for messages like this one. The exact text isn't guaranteed to
+/// stay the same between compiler versions, so don't compare it or parse it.
+pub fn expect_type<T: 'static>(value: &dyn Any) -> Result<&T, TypeMismatchError> {
+    value.downcast_ref::<T>().ok_or(TypeMismatchError {
+        expected: type_name::<T>(),
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matching_type() {
+        let value: Box<dyn Any> = Box::new(String::from("Hello"));
+        assert_eq!("Hello", expect_type::<String>(value.as_ref()).unwrap());
+    }
+
+    #[test]
+    fn mismatched_type() {
+        let value: Box<dyn Any> = Box::new(0u128);
+        let err = expect_type::<String>(value.as_ref()).unwrap_err();
+        assert!(err.expected().contains("String"));
+        assert!(err.to_string().contains("String"));
+    }
+
+    #[test]
+    fn integer_types_are_different_types() {
+        let value: Box<dyn Any> = Box::new(0i32);
+        let err = expect_type::<i64>(value.as_ref()).unwrap_err();
+        assert!(err.expected().contains("i64"));
+    }
+}
@@ -26,6 +26,9 @@ use anyhow::{Context, Result};
 use std::any::Any;
 use std::collections::HashMap;
 
+mod downcast;
+mod word_counter;
+
 fn main() {
     println!("Chapter 8!");
     vectors().unwrap();
@@ -106,6 +109,12 @@ fn multiple_types_in_vectors() {
             println!("Unable to downcast unexpected type '{:?}'", type_id)
         }
     }
+
+    // If we expect a certain type and getting something else is an error, `downcast::expect_type`
+    // gives us an error that says which type we expected.
+    if let Err(e) = downcast::expect_type::<String>(vec[2].as_ref()) {
+        println!("The third item is not a String: {}", e);
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -203,6 +212,13 @@ fn hash_maps() -> Result<()> {
         }
     }
 
+    // The map above can't tell us which word came first. See `word_counter.rs` for a counter that
+    // also remembers the order.
+    let counter = word_counter::WordCounter::from_text("hello world wonderful world");
+    for (word, count) in counter.in_order() {
+        println!("{word}: {count}");
+    }
+
     Ok(())
 }
 
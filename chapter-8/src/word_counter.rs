@@ -0,0 +1,130 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Counts words like the example in `hash_maps`, and also remembers the order in which each word
+/// was first seen.
+///
+/// A `HashMap` doesn't keep its keys in any particular order. Iterating over it gives the keys in
+/// an order that depends on their hashes, and the hasher is seeded randomly, so the order can even
+/// change from one run of the program to the next. If we want to report the words in the order
+/// they appeared, we have to keep track of that ourselves, so we keep a `Vec` of the words next to
+/// the map. (The `indexmap` crate does this for you.)
+#[derive(Debug, Default, Clone)]
+pub struct WordCounter {
+    counts: HashMap<String, usize>,
+    /// Every word in `counts`, each one exactly once, in the order they were first added.
+    order: Vec<String>,
+}
+
+impl WordCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts every whitespace-separated word in `text`.
+    pub fn from_text(text: &str) -> Self {
+        let mut counter = Self::new();
+        for word in text.split_whitespace() {
+            counter.add(word);
+        }
+        counter
+    }
+
+    /// Adds one to the count for `word`.
+    ///
+    /// `entry` gives us either a `Vacant` or an `Occupied` entry. `or_insert` hides the difference,
+    /// but here we need it: a vacant entry means this is the first time we've seen the word, so it
+    /// goes on the end of `order`. Either way we only look the word up once.
+    pub fn add(&mut self, word: &str) {
+        match self.counts.entry(word.to_string()) {
+            Entry::Vacant(entry) => {
+                self.order.push(entry.key().clone());
+                entry.insert(1);
+            }
+            Entry::Occupied(mut entry) => *entry.get_mut() += 1,
+        }
+    }
+
+    /// How many times `word` was added, or 0 if it never was.
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.get(word).copied().unwrap_or(0)
+    }
+
+    /// The number of different words.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Each word and its count, in the order the words were first seen.
+    pub fn in_order(&self) -> Vec<(&str, usize)> {
+        self.order
+            .iter()
+            .map(|word| (word.as_str(), self.counts[word]))
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "the cat saw the dog and the dog saw a bird";
+
+    #[test]
+    fn counts() {
+        let counter = WordCounter::from_text(TEXT);
+        assert_eq!(3, counter.count("the"));
+        assert_eq!(2, counter.count("dog"));
+        assert_eq!(2, counter.count("saw"));
+        assert_eq!(1, counter.count("bird"));
+        assert_eq!(0, counter.count("fish"));
+    }
+
+    #[test]
+    fn first_seen_order() {
+        let counter = WordCounter::from_text(TEXT);
+        assert_eq!(
+            vec![
+                ("the", 3),
+                ("cat", 1),
+                ("saw", 2),
+                ("dog", 2),
+                ("and", 1),
+                ("a", 1),
+                ("bird", 1),
+            ],
+            counter.in_order()
+        );
+        assert_eq!(7, counter.len());
+    }
+
+    #[test]
+    fn adding_a_seen_word_does_not_move_it() {
+        let mut counter = WordCounter::new();
+        counter.add("b");
+        counter.add("a");
+        counter.add("b");
+        assert_eq!(vec![("b", 2), ("a", 1)], counter.in_order());
+    }
+
+    #[test]
+    fn words_are_case_sensitive() {
+        let counter = WordCounter::from_text("Dog dog DOG dog");
+        assert_eq!(vec![("Dog", 1), ("dog", 2), ("DOG", 1)], counter.in_order());
+    }
+
+    #[test]
+    fn empty() {
+        let counter = WordCounter::from_text("  ");
+        assert!(counter.is_empty());
+        assert!(counter.in_order().is_empty());
+    }
+}
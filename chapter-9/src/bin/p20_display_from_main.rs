@@ -1,4 +1,4 @@
-use chapter_9::helpers::print_program_name;
+use chapter_9::helpers::{print_program_name, report_error};
 use chapter_9::mane_error::always_err;
 use std::process::ExitCode;
 
@@ -28,13 +28,13 @@ use std::process::ExitCode;
 /// }
 /// ```
 ///
-/// Prefer this:
+/// Prefer this (`report_error` also prints each underlying `source()` with "Caused by:"):
 fn main() -> ExitCode {
     print_program_name();
     match always_err() {
         Ok(_) => ExitCode::SUCCESS,
         Err(e) => {
-            eprintln!("{}", e);
+            report_error(&e);
             ExitCode::FAILURE
         }
     }
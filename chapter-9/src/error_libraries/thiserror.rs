@@ -1,4 +1,5 @@
 use crate::error_libraries::thiserror::MyError::ErrorOne;
+use std::error::Error as StdError;
 use std::fs;
 use std::path::PathBuf;
 /// https://hackernoon.com/a-comprehensive-guide-for-handling-errors-in-rust
@@ -14,6 +15,9 @@ use std::path::PathBuf;
 /// > as display_chain, which can be used to chain together multiple errors into a single error
 /// > chain.
 ///
+/// Note: `thiserror` does not actually have a `display_chain` method. See `display_chain` below for
+/// a function that does what the article describes, for any `std::error::Error`.
+///
 /// My Notes:
 ///
 /// `thiserror` is not "batteries included". It is good for defining an error to be the public-
@@ -45,3 +49,62 @@ pub fn use_thiserror() -> Result<(), MyError> {
     let _ = fs::read_to_string(&p).map_err(|e| ErrorOne { path: p, source: e })?;
     Ok(())
 }
+
+/// Renders an error and each error in its `source()` chain on one line, separated by `": "`, e.g.
+/// `"unable to start: unable to load config: file not found"`. This is the one-line version of
+/// `helpers::error_chain`.
+///
+/// This only reads well if each error's `Display` leaves out its source. `MyError` above includes
+/// `source` in its message, so its chain would print the `io::Error` twice.
+pub fn display_chain(e: &dyn StdError) -> String {
+    let mut chain = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    chain
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Error)]
+    #[error("unable to load config")]
+    struct ConfigError {
+        #[source]
+        source: std::io::Error,
+    }
+
+    #[derive(Debug, Error)]
+    #[error("unable to start")]
+    struct StartError {
+        #[source]
+        source: ConfigError,
+    }
+
+    #[test]
+    fn three_levels() {
+        let err = StartError {
+            source: ConfigError {
+                source: std::io::Error::new(std::io::ErrorKind::NotFound, "file not found"),
+            },
+        };
+        assert_eq!(
+            "unable to start: unable to load config: file not found",
+            display_chain(&err)
+        );
+    }
+
+    #[test]
+    fn no_source() {
+        let err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
+        assert_eq!("file not found", display_chain(&err));
+    }
+}
@@ -1,5 +1,7 @@
 use std::env;
+use std::error::Error;
 use std::ffi::OsStr;
+use std::fmt::Write;
 use std::path::Path;
 
 fn prog() -> String {
@@ -16,3 +18,21 @@ fn prog() -> String {
 pub fn print_program_name() {
     println!("Chapter 9! - {}", prog());
 }
+
+/// Formats the top-level `Display` message of an error followed by each error in its `source()`
+/// chain. This is like the `Debug` output of `anyhow`, but works for any `std::error::Error`.
+pub fn error_chain(e: &dyn Error) -> String {
+    let mut s = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        // Writing to a `String` cannot fail.
+        let _ = write!(s, "\n    Caused by: {}", cause);
+        source = cause.source();
+    }
+    s
+}
+
+/// Prints the error and its chain of underlying causes to `stderr`.
+pub fn report_error(e: &dyn Error) {
+    eprintln!("{}", error_chain(e));
+}
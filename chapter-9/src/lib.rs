@@ -4,6 +4,7 @@ use std::panic::catch_unwind;
 pub mod error_libraries;
 pub mod helpers;
 pub mod mane_error;
+pub mod panic_error;
 
 /// ## Panic vs Error
 ///
@@ -21,7 +22,8 @@ pub mod mane_error;
 /// > type is more appropriate to use for functions that can fail on a regular basis. Additionally,
 /// > this function is not guaranteed to catch all panics, see the “Notes” section below.
 ///
-/// Run `p01_panic_vs_error` to demonstrate what this does.
+/// Run `p01_panic_vs_error` to demonstrate what this does. See `panic_error::run_catching` for a
+/// helper that turns the caught panic into an error with the panic's message.
 pub fn panic_vs_error() {
     // Technically you can catch a panic, but it is not recommended to do so. This mechanism is used
     // for special cases such as Rust code that is being called from C.
@@ -209,6 +211,214 @@ mod custom_error {
             self.source.as_ref().map(|e| e.as_ref() as &(dyn Error))
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::helpers::error_chain;
+
+        #[test]
+        fn error_chain_prints_caused_by() {
+            let err = BestError {
+                message: "The universe imploded".to_string(),
+                kind: Kind::Implosion,
+                source: Some(Box::new(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "signal file not found",
+                ))),
+            };
+            let chain = error_chain(&err);
+            assert!(chain.starts_with("The universe imploded: signal file not found"));
+            assert!(chain.contains("\n    Caused by: signal file not found"));
+        }
+    }
+}
+
+/// # An Error with Many Sources
+/// A function that does IO and parsing can fail in more than one way. An enum with a variant for
+/// each kind of failure lets `?` convert all of them into one error type.
+mod multiple_sources {
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
+    use std::io::{self, Read};
+    use std::num::ParseIntError;
+
+    #[derive(Debug)]
+    pub enum AppError {
+        /// Reading the input failed.
+        Io(io::Error),
+        /// A line of the input was not a number.
+        Parse(ParseIntError),
+        /// The input was fine, but the `id` we were looking for wasn't in it.
+        NotFound { id: u64 },
+    }
+
+    impl Display for AppError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                AppError::Io(e) => write!(f, "unable to read the ids: {}", e),
+                AppError::Parse(e) => write!(f, "unable to parse an id: {}", e),
+                AppError::NotFound { id } => write!(f, "id {} was not found", id),
+            }
+        }
+    }
+
+    impl Error for AppError {
+        /// The variants that wrap another error return it. `NotFound` is not caused by anything
+        /// else, so it has no source.
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                AppError::Io(e) => Some(e),
+                AppError::Parse(e) => Some(e),
+                AppError::NotFound { .. } => None,
+            }
+        }
+    }
+
+    // One `From` for each error that `?` needs to convert. The `thiserror` library can generate
+    // these with `#[from]`, but there's nothing magic about them.
+
+    impl From<io::Error> for AppError {
+        fn from(e: io::Error) -> Self {
+            AppError::Io(e)
+        }
+    }
+
+    impl From<ParseIntError> for AppError {
+        fn from(e: ParseIntError) -> Self {
+            AppError::Parse(e)
+        }
+    }
+
+    /// Reads one id per line and returns `id` if it is one of them. The first `?` converts an
+    /// `io::Error` and the second converts a `ParseIntError`, both into `AppError`.
+    pub fn find_id<R: Read>(mut reader: R, id: u64) -> Result<u64, AppError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        for line in contents.lines() {
+            let found: u64 = line.trim().parse()?;
+            if found == id {
+                return Ok(found);
+            }
+        }
+        Err(AppError::NotFound { id })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// A reader that always fails, so that we can get an `io::Error` without touching the
+        /// filesystem.
+        struct BrokenReader;
+
+        impl Read for BrokenReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("the disk is on fire"))
+            }
+        }
+
+        #[test]
+        fn found() {
+            assert_eq!(2, find_id("1\n2\n3".as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn io_error_has_a_source() {
+            let err = find_id(BrokenReader, 2).unwrap_err();
+            assert!(matches!(err, AppError::Io(_)));
+            assert_eq!("the disk is on fire", err.source().unwrap().to_string());
+        }
+
+        #[test]
+        fn parse_error_has_a_source() {
+            let err = find_id("1\ntwo\n3".as_bytes(), 3).unwrap_err();
+            assert!(matches!(err, AppError::Parse(_)));
+            assert!(err.source().is_some());
+        }
+
+        #[test]
+        fn not_found_has_no_source() {
+            let err = find_id("1\n2\n3".as_bytes(), 4).unwrap_err();
+            assert!(matches!(err, AppError::NotFound { id: 4 }));
+            assert!(err.source().is_none());
+            assert_eq!("id 4 was not found", err.to_string());
+        }
+    }
+}
+
+/// # `Box<dyn Error>` vs. a Concrete Error
+/// The same function as `multiple_sources::find_id`, written twice to compare the error types.
+///
+/// - `Box<dyn Error>` is the easy one. Any error converts into it with `?`, so there is nothing to
+///   write, and it is fine when the caller is only going to print the error (like minigrep's `run`
+///   or `main`). But the caller can't `match` on it. To find out what went wrong they must guess a
+///   type and `downcast_ref` to it, and a boxed string from `.into()` can't be downcast to anything
+///   useful at all.
+/// - A concrete enum like `AppError` takes more code (a variant, a `Display` arm, and a `From` for
+///   each source), but the caller can `match` on it, and the compiler tells them when a new variant
+///   is added. This is usually what a library should return.
+mod boxed_vs_concrete {
+    use crate::multiple_sources::AppError;
+    use std::error::Error;
+    use std::io::Read;
+
+    /// Every `?` converts into `Box<dyn Error>` for free, and a `String` message can be turned into
+    /// one with `.into()`.
+    pub fn find_id_boxed<R: Read>(mut reader: R, id: u64) -> Result<u64, Box<dyn Error>> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        for line in contents.lines() {
+            let found: u64 = line.trim().parse()?;
+            if found == id {
+                return Ok(found);
+            }
+        }
+        Err(format!("id {} was not found", id).into())
+    }
+
+    /// The concrete version is `multiple_sources::find_id`.
+    pub fn find_id_concrete<R: Read>(reader: R, id: u64) -> Result<u64, AppError> {
+        crate::multiple_sources::find_id(reader, id)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::num::ParseIntError;
+
+        #[test]
+        fn both_succeed() {
+            assert_eq!(2, find_id_boxed("1\n2".as_bytes(), 2).unwrap());
+            assert_eq!(2, find_id_concrete("1\n2".as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn concrete_error_can_be_matched() {
+            let err = find_id_concrete("1\n2".as_bytes(), 7).unwrap_err();
+            let missing = match err {
+                AppError::Io(_) | AppError::Parse(_) => None,
+                AppError::NotFound { id } => Some(id),
+            };
+            assert_eq!(Some(7), missing);
+        }
+
+        #[test]
+        fn boxed_error_must_be_downcast() {
+            let err = find_id_boxed("1\ntwo".as_bytes(), 7).unwrap_err();
+            // We have to know (or guess) the type that is in the box.
+            assert!(err.downcast_ref::<ParseIntError>().is_some());
+            assert!(err.downcast_ref::<std::io::Error>().is_none());
+        }
+
+        #[test]
+        fn boxed_not_found_is_only_a_message() {
+            let err = find_id_boxed("1\n2".as_bytes(), 7).unwrap_err();
+            // There is no `NotFound` type to downcast to, and the id is only in the message.
+            assert!(err.downcast_ref::<ParseIntError>().is_none());
+            assert_eq!("id 7 was not found", err.to_string());
+        }
+    }
 }
 
 /// # The Question Mark Operator (p.160)
@@ -231,38 +441,442 @@ mod question_mark_operator {
         let _ = result()?;
         Ok(())
     }
+
+    /// A more realistic error for the examples below. `Parse` keeps the input that failed and the
+    /// message from the underlying `ParseIntError`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum DivideError {
+        Parse { input: String, reason: String },
+        DivideByZero,
+        Overflow,
+    }
+
+    impl std::fmt::Display for DivideError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DivideError::Parse { input, reason } => {
+                    write!(f, "unable to parse '{}': {}", input, reason)
+                }
+                DivideError::DivideByZero => write!(f, "cannot divide by zero"),
+                DivideError::Overflow => write!(f, "the division overflowed"),
+            }
+        }
+    }
+
+    impl std::error::Error for DivideError {}
+
+    /// `or_else` gets a chance to recover from an error: if the input isn't decimal we try again as
+    /// hex. `map_err` then converts whatever `ParseIntError` is left into our own error type.
+    ///
+    /// The `?` can't convert a `ParseIntError` into a `DivideError` on its own (there is no `From`
+    /// impl), so `map_err` does the conversion right before the `?`.
+    fn parse_number(s: &str) -> Result<i32, DivideError> {
+        let s = s.trim();
+        let n = s
+            .parse::<i32>()
+            .or_else(|e| match s.strip_prefix("0x") {
+                Some(hex) => i32::from_str_radix(hex, 16),
+                None => Err(e),
+            })
+            .map_err(|e| DivideError::Parse {
+                input: s.to_string(),
+                reason: e.to_string(),
+            })?;
+        Ok(n)
+    }
+
+    /// Parses two numbers and divides them using only combinators. `and_then` chains steps that can
+    /// fail, `map` transforms the success value. The first error short-circuits the rest.
+    pub fn divide_strs(a: &str, b: &str) -> Result<i32, DivideError> {
+        parse_number(a)
+            .and_then(|a| parse_number(b).map(|b| (a, b)))
+            .and_then(|(a, b)| {
+                if b == 0 {
+                    Err(DivideError::DivideByZero)
+                } else {
+                    a.checked_div(b).ok_or(DivideError::Overflow)
+                }
+            })
+    }
+
+    /// The same thing with `?`. `parse_number` already returns a `DivideError`, so each `?` only
+    /// has to return it early.
+    pub fn divide_strs_with_question_mark(a: &str, b: &str) -> Result<i32, DivideError> {
+        let a = parse_number(a)?;
+        let b = parse_number(b)?;
+        if b == 0 {
+            return Err(DivideError::DivideByZero);
+        }
+        a.checked_div(b).ok_or(DivideError::Overflow)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn divide_strs_success() {
+            assert_eq!(Ok(5), divide_strs("10", " 2 "));
+        }
+
+        #[test]
+        fn divide_strs_hex() {
+            assert_eq!(Ok(8), divide_strs("0x10", "2"));
+        }
+
+        #[test]
+        fn divide_strs_parse_failure() {
+            let err = divide_strs("10", "two").unwrap_err();
+            match err {
+                DivideError::Parse { input, reason } => {
+                    assert_eq!("two", input);
+                    assert_eq!("invalid digit found in string", reason);
+                }
+                other => panic!("expected a parse error, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn divide_strs_divide_by_zero() {
+            assert_eq!(Err(DivideError::DivideByZero), divide_strs("10", "0"));
+        }
+
+        #[test]
+        fn divide_strs_overflow() {
+            assert_eq!(Err(DivideError::Overflow), divide_strs("-2147483648", "-1"));
+        }
+
+        #[test]
+        fn question_mark_success() {
+            assert_eq!(Ok(5), divide_strs_with_question_mark("10", "2"));
+        }
+
+        #[test]
+        fn question_mark_parse_failure() {
+            let err = divide_strs_with_question_mark("ten", "2").unwrap_err();
+            assert_eq!(
+                "unable to parse 'ten': invalid digit found in string",
+                err.to_string()
+            );
+        }
+
+        #[test]
+        fn question_mark_hex() {
+            assert_eq!(Ok(8), divide_strs_with_question_mark("0x10", "2"));
+        }
+
+        #[test]
+        fn question_mark_matches_combinators() {
+            for (a, b) in [
+                ("10", "2"),
+                ("0x10", " 0x4"),
+                ("ten", "2"),
+                ("1", "0"),
+                ("9", "x"),
+            ] {
+                assert_eq!(divide_strs(a, b), divide_strs_with_question_mark(a, b));
+            }
+        }
+
+        #[test]
+        fn question_mark_divide_by_zero() {
+            assert_eq!(
+                Err(DivideError::DivideByZero),
+                divide_strs_with_question_mark("10", "0")
+            );
+        }
+    }
 }
 
 /// # The Question Mark Operator Calls Into (p.162)
 /// The `?` operator can convert errors if they implement `From`.
-/// For brevity the errors in this example are not "proper errors".
+/// For brevity the errors in this example do not implement `Error`.
 mod question_mark_into_call {
-    struct ErrorTypeOne;
-    struct ErrorTypeTwo;
+    use std::num::ParseIntError;
+
+    /// A low-level error that knows which input could not be parsed and why, e.g. it wasn't a
+    /// number at all, or it was a number that is too big for a `u16`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ParseError {
+        pub input: String,
+        pub reason: String,
+    }
+
+    /// A higher-level error for the whole application. It has a bit of context about what we were
+    /// doing, and the cause in a form that is ready to show to the user.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct AppError {
+        pub context: String,
+        pub cause: String,
+    }
 
-    impl From<ErrorTypeOne> for ErrorTypeTwo {
-        fn from(e: ErrorTypeOne) -> Self {
-            // pretend there is some real transformation here
-            ErrorTypeTwo
+    /// This is the conversion that `?` calls. The original input and the reason are carried over
+    /// into `cause`, so nothing is lost when the error crosses into the higher-level type.
+    impl From<ParseError> for AppError {
+        fn from(e: ParseError) -> Self {
+            AppError {
+                context: "unable to load the configuration".to_string(),
+                cause: format!("'{}' is not a valid port: {}", e.input, e.reason),
+            }
         }
     }
 
-    fn returns_error_type_one() -> Result<(), ErrorTypeOne> {
-        Err(ErrorTypeOne)
+    fn parse_port(input: &str) -> Result<u16, ParseError> {
+        input.parse().map_err(|e: ParseIntError| ParseError {
+            input: input.to_string(),
+            reason: e.to_string(),
+        })
     }
 
-    fn returns_error_type_two() -> Result<(), ErrorTypeTwo> {
-        // the conversion to ErrorTypeTwo is automatic when ? is used
-        Ok(returns_error_type_one()?)
+    /// The conversion to `AppError` is automatic when `?` is used.
+    pub fn load_port(input: &str) -> Result<u16, AppError> {
+        let port = parse_port(input)?;
+        Ok(port)
     }
 
     struct ErrorTypeThree;
 
-    // This will not compile because ErrorTypeOne cannot be automatically converted to
+    // This will not compile because ParseError cannot be automatically converted to
     // ErrorTypeThree
-    // fn returns_error_type_three() -> Result<(), ErrorTypeThree> {
-    //     Ok(returns_error_type_one()?)
+    // fn returns_error_type_three() -> Result<u16, ErrorTypeThree> {
+    //     Ok(parse_port("80")?)
     // }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn load_port_success() {
+            assert_eq!(Ok(8080), load_port("8080"));
+        }
+
+        #[test]
+        fn load_port_error_keeps_input() {
+            let err = load_port("eighty").unwrap_err();
+            assert_eq!("unable to load the configuration", err.context);
+            assert_eq!(
+                "'eighty' is not a valid port: invalid digit found in string",
+                err.cause
+            );
+        }
+
+        #[test]
+        fn load_port_too_large() {
+            let err = load_port("99999").unwrap_err();
+            assert_eq!(
+                "'99999' is not a valid port: number too large to fit in target type",
+                err.cause
+            );
+        }
+
+        #[test]
+        fn from_parse_error() {
+            let err = AppError::from(ParseError {
+                input: "".to_string(),
+                reason: "cannot parse integer from empty string".to_string(),
+            });
+            assert_eq!(
+                "'' is not a valid port: cannot parse integer from empty string",
+                err.cause
+            );
+        }
+    }
+}
+
+/// # A Pipeline of Conversions
+/// Input often goes through a few stages: raw text, then something cleaned up, then something we
+/// know is valid. Giving each stage its own type and a `From` or `TryFrom` between them means a
+/// function can ask for exactly the stage it needs, e.g. a `Validated`, and can't be handed one
+/// that skipped a step. `into()` and `?` then make the pipeline itself short.
+mod conversion_pipeline {
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
+
+    /// A username exactly as the user typed it.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Raw(pub String);
+
+    /// A username with the surrounding whitespace trimmed and in lowercase. Getting here can't
+    /// fail, so it is a `From`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Parsed(String);
+
+    /// A username that follows the rules. The field is private, so `TryFrom` is the only way to
+    /// make one.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Validated(String);
+
+    impl Validated {
+        pub const MAX_LEN: usize = 16;
+
+        pub fn as_str(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// `From` gives us `Into` for free, so `let parsed: Parsed = raw.into()` works too.
+    impl From<Raw> for Parsed {
+        fn from(raw: Raw) -> Self {
+            Parsed(raw.0.trim().to_lowercase())
+        }
+    }
+
+    /// Why a `Parsed` can't become a `Validated`.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ValidationError {
+        Empty,
+        TooLong { len: usize },
+        InvalidChar(char),
+    }
+
+    impl Display for ValidationError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ValidationError::Empty => write!(f, "the username is empty"),
+                ValidationError::TooLong { len } => write!(
+                    f,
+                    "the username is {} characters long, the limit is {}",
+                    len,
+                    Validated::MAX_LEN
+                ),
+                ValidationError::InvalidChar(c) => {
+                    write!(f, "the username cannot contain '{}'", c)
+                }
+            }
+        }
+    }
+
+    impl Error for ValidationError {}
+
+    /// Only accepts a `Parsed`, so there's no need to trim or lowercase again here.
+    impl TryFrom<Parsed> for Validated {
+        type Error = ValidationError;
+
+        fn try_from(parsed: Parsed) -> Result<Self, Self::Error> {
+            let len = parsed.0.chars().count();
+            if len == 0 {
+                return Err(ValidationError::Empty);
+            }
+            if len > Validated::MAX_LEN {
+                return Err(ValidationError::TooLong { len });
+            }
+            if let Some(c) = parsed
+                .0
+                .chars()
+                .find(|c| !c.is_ascii_alphanumeric() && *c != '_')
+            {
+                return Err(ValidationError::InvalidChar(c));
+            }
+            Ok(Validated(parsed.0))
+        }
+    }
+
+    /// Anything that can go wrong in `process`. Validation is one stage of it, so its error is
+    /// wrapped, and the `From` below lets `?` do the wrapping.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ProcessError {
+        Invalid(ValidationError),
+        /// The name is valid, but it is one that we keep for ourselves.
+        Reserved(String),
+    }
+
+    impl Display for ProcessError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ProcessError::Invalid(_) => write!(f, "invalid username"),
+                ProcessError::Reserved(name) => write!(f, "the username '{}' is reserved", name),
+            }
+        }
+    }
+
+    impl Error for ProcessError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                ProcessError::Invalid(e) => Some(e),
+                ProcessError::Reserved(_) => None,
+            }
+        }
+    }
+
+    impl From<ValidationError> for ProcessError {
+        fn from(e: ValidationError) -> Self {
+            ProcessError::Invalid(e)
+        }
+    }
+
+    const RESERVED: [&str; 2] = ["admin", "root"];
+
+    /// Takes a username from `Raw` all the way to `Validated`. Each line is one stage: `into()`
+    /// can't fail, and `?` turns a `ValidationError` into a `ProcessError`.
+    pub fn process(raw: Raw) -> Result<Validated, ProcessError> {
+        let parsed: Parsed = raw.into();
+        let validated = Validated::try_from(parsed)?;
+        if RESERVED.contains(&validated.as_str()) {
+            return Err(ProcessError::Reserved(validated.0));
+        }
+        Ok(validated)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn raw(s: &str) -> Raw {
+            Raw(s.to_string())
+        }
+
+        #[test]
+        fn passes_every_stage() {
+            let validated = process(raw("  Ferris_The_Crab ")).unwrap();
+            assert_eq!("ferris_the_crab", validated.as_str());
+        }
+
+        #[test]
+        fn parsing_trims_and_lowercases() {
+            assert_eq!(
+                Parsed("ferris".to_string()),
+                Parsed::from(raw("\tFERRIS\n"))
+            );
+        }
+
+        #[test]
+        fn fails_validation() {
+            let err = process(raw("ferris the crab")).unwrap_err();
+            assert_eq!(
+                ProcessError::Invalid(ValidationError::InvalidChar(' ')),
+                err
+            );
+            assert_eq!(
+                "the username cannot contain ' '",
+                err.source().unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn empty_after_trimming() {
+            assert_eq!(
+                Err(ProcessError::Invalid(ValidationError::Empty)),
+                process(raw("   "))
+            );
+        }
+
+        #[test]
+        fn too_long() {
+            assert_eq!(
+                Err(ProcessError::Invalid(ValidationError::TooLong { len: 17 })),
+                process(raw("a_very_long_name_"))
+            );
+            assert!(process(raw("a_very_long_name")).is_ok());
+        }
+
+        #[test]
+        fn reserved() {
+            let err = process(raw(" Admin")).unwrap_err();
+            assert_eq!(ProcessError::Reserved("admin".to_string()), err);
+            assert!(err.source().is_none());
+        }
+    }
 }
 
 /// # Custom Types for Validation (p. 167)
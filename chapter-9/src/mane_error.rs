@@ -1,5 +1,7 @@
+use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 
 // pub fn run_program() -> Result<(), ManeError> {
 //     println!("Chapter 9!");
@@ -8,22 +10,79 @@ use std::fmt::{Debug, Display, Formatter};
 // }
 
 pub fn always_err() -> std::result::Result<(), ManeError> {
-    Err(ManeError)
+    Err(ManeError::default())
 }
 
 /// This error tells us whether it has been printed with `Debug` or `Display`.
-pub struct ManeError;
+pub struct ManeError {
+    message: String,
+}
+
+impl ManeError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Default for ManeError {
+    fn default() -> Self {
+        Self::from("Something went wrong in main")
+    }
+}
+
+impl From<&str> for ManeError {
+    fn from(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+/// This lets us write `"boom".parse::<ManeError>()`. Any string is a valid message, so parsing can
+/// never fail. `Infallible` is the error type for conversions that can't fail.
+impl FromStr for ManeError {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
 
 impl Display for ManeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        Display::fmt("Error: This is the DISPLAY print", f)
+        write!(f, "DISPLAY: {}", self.message)
     }
 }
 
 impl Debug for ManeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        Display::fmt("Error: This is the DEBUG print", f)
+        write!(f, "DEBUG: {}", self.message)
     }
 }
 
 impl Error for ManeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips_message() {
+        let e = "boom".parse::<ManeError>().unwrap();
+        assert_eq!("boom", e.message());
+        assert_eq!("DISPLAY: boom", e.to_string());
+        assert_eq!("DEBUG: boom", format!("{:?}", e));
+    }
+
+    #[test]
+    fn from_str_slice() {
+        let e = ManeError::from("kaboom");
+        assert_eq!("DISPLAY: kaboom", e.to_string());
+    }
+
+    #[test]
+    fn always_err_has_default_message() {
+        let e = always_err().unwrap_err();
+        assert_eq!("DISPLAY: Something went wrong in main", e.to_string());
+    }
+}
@@ -0,0 +1,89 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, UnwindSafe};
+
+/// The error returned by `run_catching` when the function it ran panicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicError {
+    message: String,
+}
+
+impl PanicError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for PanicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "panicked: {}", self.message)
+    }
+}
+
+impl Error for PanicError {}
+
+/// Runs `f` and turns a panic into an `Err(PanicError)`. (See `panic_vs_error` in `lib.rs` for why
+/// you should rarely need this.)
+///
+/// `catch_unwind` gives us the panic's *payload* as a `Box<dyn Any + Send>`, which is whatever was
+/// passed to `panic!`. `panic!("boom")` passes a `&'static str`, and `panic!("{}", x)` formats a
+/// `String`, so those are the two types we try to `downcast` to. Anything else (e.g.
+/// `std::panic::panic_any(42)`) gets a generic message.
+///
+/// `UnwindSafe` is a marker trait that the compiler uses to stop us from catching a panic while
+/// holding a `&mut` to something that the panic may have left half-updated. Closures that capture
+/// only owned values or shared references are usually `UnwindSafe` automatically.
+///
+/// Note that the panic message is still printed to `stderr` by the panic hook, and that nothing is
+/// caught if the program is built with `panic = "abort"`.
+pub fn run_catching<T, F: FnOnce() -> T + UnwindSafe>(f: F) -> Result<T, PanicError> {
+    catch_unwind(f).map_err(|payload| PanicError {
+        message: payload_message(payload.as_ref()),
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ok_when_no_panic() {
+        assert_eq!(Ok(42), run_catching(|| 42));
+    }
+
+    #[test]
+    fn str_payload() {
+        let err = run_catching(|| panic!("boom")).unwrap_err();
+        assert_eq!("boom", err.message());
+    }
+
+    #[test]
+    fn string_payload() {
+        let what = "boom";
+        let err = run_catching(|| panic!("{} {}", what, what)).unwrap_err();
+        assert_eq!("boom boom", err.message());
+    }
+
+    #[test]
+    fn other_payload() {
+        let err = run_catching(|| std::panic::panic_any(42)).unwrap_err();
+        assert_eq!("unknown panic payload", err.message());
+    }
+
+    #[test]
+    fn display() {
+        let err = run_catching(|| panic!("boom")).unwrap_err();
+        assert_eq!("panicked: boom", err.to_string());
+    }
+}